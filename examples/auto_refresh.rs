@@ -1,126 +1,54 @@
-#![allow(clippy::missing_errors_doc)]
+//! A refreshable client renews its own access token when it expires, so it
+//! can be shared between tasks without any external locking.
+//!
+//! New tokens are saved to a JSON file as soon as they are issued.
 
-use std::future::Future;
+use std::{path::PathBuf, sync::Arc};
 
-use monzo::{inner_client::Refreshable, Balance, Pot};
-use serde::{Deserialize, Serialize};
-use tokio::sync::{Mutex, RwLock};
+use clap::Parser;
+use monzo::{token_store::JsonFile, Client};
 
-fn main() {
-    // no op
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Auth {
-    access_token: String,
+#[derive(Parser)]
+struct Args {
+    #[clap[long, env]]
     client_id: String,
+
+    #[clap[long, env]]
     client_secret: String,
-    refresh_token: String,
-}
 
-#[derive(Debug)]
-pub struct Client {
-    client: RwLock<monzo::Client<Refreshable>>,
-    refresh_lock: Mutex<()>,
+    /// Path to a JSON file containing the access and refresh tokens
+    #[clap[long, env, default_value = "tokens.json"]]
+    tokens: PathBuf,
 }
 
-impl Client {
-    pub async fn auth(&self) -> Auth {
-        let client = self.client.read().await;
-
-        Auth {
-            access_token: client.access_token().clone(),
-            client_id: client.client_id().clone(),
-            client_secret: client.client_secret().clone(),
-            refresh_token: client.refresh_token().clone(),
-        }
-    }
-
-    pub async fn balance(&self, account_id: &str) -> monzo::Result<Balance> {
-        self.with_retry(|| async { self.client.read().await.balance(account_id).await })
-            .await
-    }
-
-    pub async fn pots(&self, account_id: &str) -> monzo::Result<Vec<Pot>> {
-        self.with_retry(|| async { self.client.read().await.pots(account_id).await })
-            .await
-    }
-
-    pub async fn withdraw_from_pot(
-        &self,
-        pot_id: &str,
-        destination_account_id: &str,
-        amount: u32,
-    ) -> monzo::Result<Pot> {
-        self.with_retry(|| async {
-            self.client
-                .read()
-                .await
-                .withdraw_from_pot(pot_id, destination_account_id, amount)
-                .await
+#[tokio::main]
+async fn main() -> monzo::Result<()> {
+    let args = Args::parse();
+
+    let client = Arc::new(Client::from_token_store(
+        args.client_id,
+        args.client_secret,
+        JsonFile::new(args.tokens),
+    )?);
+
+    let tasks: Vec<_> = client
+        .accounts()
+        .send()
+        .await?
+        .into_iter()
+        .map(|account| {
+            let client = Arc::clone(&client);
+            tokio::spawn(async move {
+                let balance = client.balance(&account.id).await?;
+                monzo::Result::Ok((account.id, balance))
+            })
         })
-        .await
-    }
+        .collect();
 
-    pub async fn deposit_into_pot(
-        &self,
-        pot_id: &str,
-        source_account_id: &str,
-        amount: u32,
-    ) -> monzo::Result<Pot> {
-        self.with_retry(|| async {
-            self.client
-                .read()
-                .await
-                .deposit_into_pot(pot_id, source_account_id, amount)
-                .await
-        })
-        .await
+    for task in tasks {
+        let (account_id, balance) = task.await.expect("task panicked")?;
+        println!("{account_id}: {}", balance.balance);
     }
 
-    async fn with_retry<F, Fut, R>(&self, f: F) -> monzo::Result<R>
-    where
-        F: Fn() -> Fut + Send + Sync,
-        Fut: Future<Output = monzo::Result<R>> + Send,
-        R: Send,
-    {
-        let response = f().await;
-
-        if response.is_err() {
-            tracing::warn!("authentication failed, access token may have expired");
-            self.refresh_auth().await?;
-            return f().await;
-        }
-
-        response
-    }
-
-    async fn refresh_auth(&self) -> monzo::Result<()> {
-        tracing::info!("attempting access token refresh");
-
-        let Ok(_refresh_lock) = self.refresh_lock.try_lock() else {
-            tracing::debug!("another thread is already refreshing auth");
-            return Ok(());
-        };
-
-        self.client.write().await.refresh_auth().await?;
-        tracing::info!("access token refreshed");
-
-        Ok(())
-    }
-}
-
-impl From<Auth> for Client {
-    fn from(auth: Auth) -> Self {
-        let client = monzo::Client::new(auth.access_token).with_refresh_tokens(
-            auth.client_id,
-            auth.client_secret,
-            auth.refresh_token,
-        );
-
-        Self {
-            client: RwLock::new(client),
-            refresh_lock: Mutex::new(()),
-        }
-    }
+    Ok(())
 }
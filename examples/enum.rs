@@ -15,8 +15,8 @@ enum MonzoClient {
 impl MonzoClient {
     async fn accounts(&self) -> Result<Vec<Account>> {
         match self {
-            Self::Quick(client) => client.accounts().await,
-            Self::Refreshable(client) => client.accounts().await,
+            Self::Quick(client) => client.accounts().send().await,
+            Self::Refreshable(client) => client.accounts().send().await,
         }
     }
 }
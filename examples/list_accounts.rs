@@ -11,7 +11,7 @@ async fn main() -> monzo::Result<()> {
     let args = Args::parse();
     let client = Client::new(args.access_token);
 
-    for account in client.accounts().await? {
+    for account in client.accounts().send().await? {
         println!("{account:#?}");
     }
 
@@ -16,7 +16,7 @@ async fn main() -> monzo::Result<()> {
     let args = Args::parse();
     let client = Client::new(args.access_token);
 
-    let accounts = client.accounts().await?;
+    let accounts = client.accounts().send().await?;
     let account_id = &accounts[0].id;
 
     let transactions = client
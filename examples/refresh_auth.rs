@@ -16,7 +16,7 @@ struct Args {
 #[tokio::main]
 async fn main() -> monzo::Result<()> {
     let args = Args::parse();
-    let mut client = Client::new("DUMMY_ACCESS_TOKEN").with_refresh_tokens(
+    let client = Client::new("DUMMY_ACCESS_TOKEN").with_refresh_tokens(
         args.client_id,
         args.client_secret,
         args.refresh_token,
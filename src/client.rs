@@ -6,11 +6,21 @@ use serde::{de::DeserializeOwned, Deserialize};
 use tracing::instrument;
 
 use crate::{
-    endpoints::{accounts, balance, feed_items, pots, transactions, who_am_i, Endpoint},
-    Result,
+    endpoints::{
+        accounts, attachments, balance, feed_items, pots, receipts, transactions, webhooks,
+        who_am_i, Endpoint,
+    },
+    money::Money,
+    Account, Error, Result,
 };
 
+mod builder;
+pub use builder::ClientBuilder;
 pub mod inner;
+pub mod rate_limit;
+pub use rate_limit::RateLimiter;
+mod retry;
+pub use retry::RetryPolicy;
 
 /// A generic trait of any HTTP client which also stores and manages an access
 /// token.
@@ -24,8 +34,8 @@ pub trait Inner: Send + Sync + std::fmt::Debug {
     where
         E: Endpoint;
 
-    /// Return a reference to the current access token
-    fn access_token(&self) -> &String;
+    /// Return the current access token
+    fn access_token(&self) -> String;
 
     /// Manually set the access token
     fn set_access_token(&mut self, access_token: String);
@@ -33,34 +43,86 @@ pub trait Inner: Send + Sync + std::fmt::Debug {
     /// The base URL of the API
     fn url(&self) -> &str;
 
-    #[instrument(skip(self, endpoint), fields(url = self.url(), endpoint = endpoint.endpoint()))]
+    /// The underlying HTTP client
+    fn http_client(&self) -> &reqwest::Client;
+
+    /// The timeout applied to each request, if any
+    fn timeout(&self) -> Option<std::time::Duration> {
+        None
+    }
+
+    /// The policy for retrying failed requests
+    fn retry_policy(&self) -> RetryPolicy;
+
+    /// Set the policy for retrying failed requests
+    fn set_retry_policy(&mut self, retry_policy: RetryPolicy);
+
+    /// The rate limiter applied to outgoing requests, if any
+    fn rate_limiter(&self) -> Option<&RateLimiter>;
+
+    /// Set the rate limiter applied to outgoing requests
+    fn set_rate_limiter(&mut self, rate_limiter: Option<RateLimiter>);
+
+    /// Send a request to the given Endpoint and deserialise the response
     fn handle_request<E, R>(&self, endpoint: &E) -> impl Future<Output = Result<R>> + Send
     where
         R: DeserializeOwned,
         E: Endpoint,
     {
-        async {
-            tracing::info!("sending request");
-            let response = self.execute(endpoint).await?;
-            tracing::info!("response received");
-
-            let result = handle_response(response).await;
-
-            match &result {
-                Ok(_) => {
-                    tracing::info!("request successful");
-                }
-                Err(e) => {
-                    tracing::info!("request failed: {}", e);
-                }
-            }
-            result
+        send_request(self, endpoint)
+    }
+}
+
+#[instrument(skip(client, endpoint), fields(url = client.url(), endpoint = endpoint.endpoint()))]
+pub async fn send_request<C, E, R>(client: &C, endpoint: &E) -> Result<R>
+where
+    C: Inner + ?Sized,
+    R: DeserializeOwned,
+    E: Endpoint,
+{
+    let retry_policy = client.retry_policy();
+    let mut attempt = 0;
+
+    loop {
+        let delay = match send_request_once(client, endpoint).await {
+            Err(e) if endpoint.idempotent() => match retry_policy.delay(attempt, &e) {
+                Some(delay) => delay,
+                None => return Err(e),
+            },
+            result => return result,
+        };
+
+        attempt += 1;
+        tracing::info!("retrying in {:?} (attempt {})", delay, attempt);
+        tokio::time::sleep(delay).await;
+    }
+}
+
+async fn send_request_once<C, E, R>(client: &C, endpoint: &E) -> Result<R>
+where
+    C: Inner + ?Sized,
+    R: DeserializeOwned,
+    E: Endpoint,
+{
+    tracing::info!("sending request");
+    let response = client.execute(endpoint).await?;
+    tracing::info!("response received");
+
+    let result = handle_response(response).await;
+
+    match &result {
+        Ok(_) => {
+            tracing::info!("request successful");
+        }
+        Err(e) => {
+            tracing::info!("request failed: {}", e);
         }
     }
+    result
 }
 
 /// A Monzo API client
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Client<C>
 where
     C: Inner,
@@ -72,9 +134,9 @@ impl<C> Client<C>
 where
     C: Inner,
 {
-    /// Return a reference to the current access token
+    /// Return the current access token
     #[must_use]
-    pub fn access_token(&self) -> &String {
+    pub fn access_token(&self) -> String {
         self.inner_client.access_token()
     }
 
@@ -83,11 +145,39 @@ where
         self.inner_client.set_access_token(access_token.into());
     }
 
+    /// Set the policy for retrying requests which fail with transient errors.
+    ///
+    /// Defaults to [`RetryPolicy::default`]. Use [`RetryPolicy::none`] to
+    /// disable retries.
+    #[must_use]
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.inner_client.set_retry_policy(retry_policy);
+        self
+    }
+
+    /// Limit the rate at which this client sends requests.
+    ///
+    /// The [`RateLimiter`] is shared by all clones of this client.
+    #[must_use]
+    pub fn with_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.inner_client.set_rate_limiter(Some(rate_limiter));
+        self
+    }
+
+    /// Return a reference to the client's rate limiter, if it has one
+    #[must_use]
+    pub fn rate_limiter(&self) -> Option<&RateLimiter> {
+        self.inner_client.rate_limiter()
+    }
+
     /// Return a list of accounts
     ///
+    /// Closed accounts are left out unless
+    /// [`include_closed`](accounts::List::include_closed) is set.
+    ///
     /// # Example
     /// ```no_run
-    /// # use monzo::{Client};
+    /// # use monzo::{accounts::Kind, Client};
     /// #
     /// # #[tokio::main]
     /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -95,18 +185,20 @@ where
     /// # let ACCESS_TOKEN = "ACCESS TOKEN";
     /// # let client = Client::new(ACCESS_TOKEN);
     /// #
-    /// let accounts = client.accounts().await?;
+    /// let accounts = client.accounts().send().await?;
+    ///
+    /// let joint_accounts = client
+    ///     .accounts()
+    ///     .account_type(Kind::UkRetailJoint)
+    ///     .include_closed(true)
+    ///     .send()
+    ///     .await?;
     /// #
     /// # Ok(())
     /// # }
-    pub async fn accounts(&self) -> Result<Vec<accounts::Account>> {
-        #[derive(Deserialize)]
-        pub struct Response {
-            accounts: Vec<accounts::Account>,
-        }
-        let response: Response = self.inner_client.handle_request(&accounts::List).await?;
-
-        Ok(response.accounts)
+    /// ```
+    pub const fn accounts(&self) -> accounts::List<'_, C> {
+        accounts::List::new(&self.inner_client)
     }
 
     /// Return the balance of a given account
@@ -198,28 +290,121 @@ where
         feed_items::basic::Request::new(&self.inner_client, account_id, title, image_url)
     }
 
-    /// Deposit money into a pot
-    pub async fn deposit_into_pot(
-        &self,
-        pot_id: &str,
-        source_account_id: &str,
-        amount: u32,
-    ) -> Result<pots::Pot> {
+    /// Create a new pot
+    ///
+    /// # Example
+    /// ```no_run
+    /// use monzo::{Client, Currency, Money};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let access_token = "ACCESS_TOKEN";
+    /// # let client = Client::new(access_token);
+    /// #
+    /// let accounts = client.accounts().send().await?;
+    /// let account = &accounts[0];
+    ///
+    /// let pot = client
+    ///     .create_pot(account, "Car insurance")
+    ///     .goal_amount(&Money::new(60000, Currency::GBP))
+    ///     .send()
+    ///     .await?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn create_pot<'a>(
+        &'a self,
+        current_account: &'a Account,
+        name: &'a str,
+    ) -> pots::Create<'a, C> {
+        pots::Create::new(&self.inner_client, current_account, name)
+    }
+
+    /// Update the name, goal or style of a pot
+    ///
+    /// # Example
+    /// ```no_run
+    /// use monzo::{Client, Currency, Money};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let access_token = "ACCESS_TOKEN";
+    /// # let client = Client::new(access_token);
+    /// #
+    /// let pots = client.pots("ACCOUNT_ID").await?;
+    /// let pot = &pots[0];
+    ///
+    /// let pot = client
+    ///     .update_pot(pot)
+    ///     .name("Car insurance 2027")
+    ///     .goal_amount(&Money::new(65000, Currency::GBP))
+    ///     .send()
+    ///     .await?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn update_pot<'a>(&'a self, pot: &pots::Pot) -> pots::Update<'a, C> {
+        pots::Update::new(&self.inner_client, pot)
+    }
+
+    /// Close a pot
+    ///
+    /// The pot must be empty. The returned [`Pot`](pots::Pot) is marked as
+    /// deleted.
+    pub async fn delete_pot(&self, pot_id: &str) -> Result<pots::Pot> {
         self.inner_client
-            .handle_request(&pots::Deposit::new(pot_id, source_account_id, amount))
+            .handle_request(&pots::Delete::new(pot_id))
             .await
     }
 
+    /// Deposit money into a pot
+    ///
+    /// The amount must be in the pot's currency, and can't be negative.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use monzo::{Client, Currency, Money};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let access_token = "ACCESS_TOKEN";
+    /// # let client = Client::new(access_token);
+    /// #
+    /// let account_id = "ACCOUNT_ID";
+    /// let pots = client.pots(account_id).await?;
+    /// let pot = &pots[0];
+    /// let amount = Money::new(1000, Currency::GBP);
+    ///
+    /// let request = client
+    ///     .deposit_into_pot(pot, account_id, &amount)
+    ///     .with_operation_id("ledger-entry-1234");
+    ///
+    /// // store the dedupe id for reconciliation
+    /// let dedupe_id = request.dedupe_id().to_string();
+    ///
+    /// let pot = request.send().await?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn deposit_into_pot<'a>(
+        &'a self,
+        pot: &'a pots::Pot,
+        source_account_id: &'a str,
+        amount: &Money,
+    ) -> pots::Deposit<'a, C> {
+        pots::Deposit::new(&self.inner_client, pot, source_account_id, amount)
+    }
+
     /// Withdraw money from a pot
-    pub async fn withdraw_from_pot(
-        &self,
-        pot_id: &str,
-        destination_account_id: &str,
-        amount: u32,
-    ) -> Result<pots::Pot> {
-        self.inner_client
-            .handle_request(&pots::Withdraw::new(pot_id, destination_account_id, amount))
-            .await
+    ///
+    /// See [`Client::deposit_into_pot`] for how to control the dedupe id.
+    pub fn withdraw_from_pot<'a>(
+        &'a self,
+        pot: &'a pots::Pot,
+        destination_account_id: &'a str,
+        amount: &Money,
+    ) -> pots::Withdraw<'a, C> {
+        pots::Withdraw::new(&self.inner_client, pot, destination_account_id, amount)
     }
 
     /// Get a list of transactions
@@ -282,6 +467,293 @@ where
         transactions::Get::new(&self.inner_client, transaction_id)
     }
 
+    /// Add, update or remove metadata on a transaction
+    ///
+    /// # Example
+    /// ```no_run
+    /// use monzo::Client;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let access_token = "ACCESS_TOKEN";
+    /// # let client = Client::new(access_token);
+    /// #
+    /// let transaction_id = "TRANSACTION_ID";
+    ///
+    /// let transaction = client
+    ///     .annotate_transaction(transaction_id)
+    ///     .metadata("cost_centre", "CC-42")
+    ///     .remove_metadata("project")
+    ///     .send()
+    ///     .await?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn annotate_transaction<'a>(
+        &'a self,
+        transaction_id: &str,
+    ) -> transactions::Annotate<'a, C> {
+        transactions::Annotate::new(&self.inner_client, transaction_id)
+    }
+
+    /// Return a list of the webhooks registered against an account
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use monzo::Client;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let access_token = "ACCESS_TOKEN";
+    /// # let client = Client::new(access_token);
+    /// #
+    /// let account_id = "ACCOUNT_ID";
+    ///
+    /// let webhooks = client.webhooks(account_id).await?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn webhooks(&self, account_id: &str) -> Result<Vec<webhooks::Webhook>> {
+        #[derive(Deserialize)]
+        struct Response {
+            webhooks: Vec<webhooks::Webhook>,
+        }
+
+        let response: Response = self
+            .inner_client
+            .handle_request(&webhooks::List::new(account_id))
+            .await?;
+
+        Ok(response.webhooks)
+    }
+
+    /// Register a webhook against an account
+    ///
+    /// Monzo will send a POST request to `url` each time an event occurs on
+    /// the account.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use monzo::Client;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let access_token = "ACCESS_TOKEN";
+    /// # let client = Client::new(access_token);
+    /// #
+    /// let account_id = "ACCOUNT_ID";
+    /// let url = "https://example.com/monzo";
+    ///
+    /// let webhook = client.register_webhook(account_id, url).await?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn register_webhook(&self, account_id: &str, url: &str) -> Result<webhooks::Webhook> {
+        #[derive(Deserialize)]
+        struct Response {
+            webhook: webhooks::Webhook,
+        }
+
+        let response: Response = self
+            .inner_client
+            .handle_request(&webhooks::Register::new(account_id, url))
+            .await?;
+
+        Ok(response.webhook)
+    }
+
+    /// Delete a webhook
+    pub async fn delete_webhook(&self, webhook_id: &str) -> Result<()> {
+        #[derive(Deserialize)]
+        struct Response {}
+
+        let _: Response = self
+            .inner_client
+            .handle_request(&webhooks::Delete::new(webhook_id))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Request a temporary URL to upload an attachment to
+    ///
+    /// The file must be uploaded with a `PUT` request to the
+    /// [`upload_url`](attachments::UploadUrl::upload_url), and then registered
+    /// using [`Client::register_attachment`]. [`Client::upload_attachment`]
+    /// does all of this in one step.
+    pub async fn attachment_upload_url(
+        &self,
+        file_name: &str,
+        file_type: &str,
+        content_length: u64,
+    ) -> Result<attachments::UploadUrl> {
+        self.inner_client
+            .handle_request(&attachments::Upload::new(
+                file_name,
+                file_type,
+                content_length,
+            ))
+            .await
+    }
+
+    /// Register an uploaded file as an attachment of a transaction
+    pub async fn register_attachment(
+        &self,
+        transaction_id: &str,
+        file_url: &str,
+        file_type: &str,
+    ) -> Result<attachments::Attachment> {
+        #[derive(Deserialize)]
+        struct Response {
+            attachment: attachments::Attachment,
+        }
+
+        let response: Response = self
+            .inner_client
+            .handle_request(&attachments::Register::new(
+                transaction_id,
+                file_url,
+                file_type,
+            ))
+            .await?;
+
+        Ok(response.attachment)
+    }
+
+    /// Upload a file and attach it to a transaction
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use monzo::Client;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let access_token = "ACCESS_TOKEN";
+    /// # let client = Client::new(access_token);
+    /// #
+    /// let transaction_id = "TRANSACTION_ID";
+    /// let receipt = std::fs::read("receipt.png")?;
+    ///
+    /// let attachment = client
+    ///     .upload_attachment(transaction_id, "receipt.png", "image/png", receipt)
+    ///     .await?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn upload_attachment(
+        &self,
+        transaction_id: &str,
+        file_name: &str,
+        file_type: &str,
+        contents: Vec<u8>,
+    ) -> Result<attachments::Attachment> {
+        let upload_url = self
+            .attachment_upload_url(file_name, file_type, contents.len() as u64)
+            .await?;
+
+        // the upload goes to a storage service rather than the Monzo API, so
+        // it's sent without the access token
+        let mut request = self
+            .inner_client
+            .http_client()
+            .put(&upload_url.upload_url)
+            .header(reqwest::header::CONTENT_TYPE, file_type)
+            .body(contents);
+
+        if let Some(timeout) = self.inner_client.timeout() {
+            request = request.timeout(timeout);
+        }
+
+        let response = request.send().await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(Error::Upload { status, body });
+        }
+
+        self.register_attachment(transaction_id, &upload_url.file_url, file_type)
+            .await
+    }
+
+    /// Remove an attachment from a transaction
+    pub async fn deregister_attachment(&self, attachment_id: &str) -> Result<()> {
+        #[derive(Deserialize)]
+        struct Response {}
+
+        let _: Response = self
+            .inner_client
+            .handle_request(&attachments::Deregister::new(attachment_id))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Create or replace the receipt for a transaction
+    ///
+    /// # Example
+    /// ```no_run
+    /// use monzo::{
+    ///     receipts::{Item, Receipt},
+    ///     Client, Currency, Money,
+    /// };
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let access_token = "ACCESS_TOKEN";
+    /// # let client = Client::new(access_token);
+    /// #
+    /// let transaction = client.transaction("TRANSACTION_ID").send().await?;
+    ///
+    /// let receipt = Receipt::for_transaction(&transaction, "RECEIPT_ID")
+    ///     .item(Item::new("Coffee", Money::new(300, Currency::GBP)))
+    ///     .item(Item::new("Croissant", Money::new(250, Currency::GBP)))
+    ///     .build()?;
+    ///
+    /// client.upload_receipt(&receipt).await?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn upload_receipt(&self, receipt: &receipts::Receipt) -> Result<()> {
+        #[derive(Deserialize)]
+        struct Response {}
+
+        let _: Response = self
+            .inner_client
+            .handle_request(&receipts::Put::new(receipt))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Return a receipt, using the external ID it was uploaded with
+    pub async fn receipt(&self, external_id: &str) -> Result<receipts::Receipt> {
+        #[derive(Deserialize)]
+        struct Response {
+            receipt: receipts::Receipt,
+        }
+
+        let response: Response = self
+            .inner_client
+            .handle_request(&receipts::Get::new(external_id))
+            .await?;
+
+        Ok(response.receipt)
+    }
+
+    /// Delete a receipt, using the external ID it was uploaded with
+    pub async fn delete_receipt(&self, external_id: &str) -> Result<()> {
+        #[derive(Deserialize)]
+        struct Response {}
+
+        let _: Response = self
+            .inner_client
+            .handle_request(&receipts::Delete::new(external_id))
+            .await?;
+
+        Ok(())
+    }
+
     /// Return information about the current session
     pub async fn who_am_i(&self) -> Result<who_am_i::Response> {
         self.inner_client.handle_request(&who_am_i::Request).await
@@ -294,9 +766,33 @@ where
 {
     let status = response.status();
 
+    if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|retry_after| retry_after.to_str().ok())
+            .and_then(retry::parse_retry_after);
+
+        return Err(Error::TooManyRequests { retry_after });
+    }
+
+    if status.is_redirection() {
+        // redirects are followed by the HTTP client, so this one couldn't be
+        let location = response
+            .headers()
+            .get(reqwest::header::LOCATION)
+            .and_then(|location| location.to_str().ok())
+            .map(ToString::to_string);
+
+        return Err(Error::UnexpectedStatus { status, location });
+    }
+
+    let body = response.bytes().await?;
+
     if status.is_success() {
-        Ok(serde_json::from_slice(&response.bytes().await?)?)
+        let deserializer = &mut serde_json::Deserializer::from_slice(&body);
+        serde_path_to_error::deserialize(deserializer).map_err(|e| Error::decode(e, &body))
     } else {
-        Err(status.into())
+        Err(Error::from_response(status, &body))
     }
 }
@@ -0,0 +1,150 @@
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+
+use crate::{
+    client::{
+        inner::{Quick, Refreshable},
+        Client, RateLimiter, RetryPolicy,
+    },
+    endpoints::auth,
+    token_store::TokenStore,
+    Result,
+};
+
+/// A builder for configuring a [`Client`].
+///
+/// # Example
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use monzo::Client;
+/// use reqwest::header::HeaderValue;
+///
+/// let client = Client::builder()
+///     .url("http://localhost:8080")
+///     .timeout(Duration::from_secs(10))
+///     .user_agent(HeaderValue::from_static("my-app/1.0"))
+///     .build("ACCESS_TOKEN");
+/// ```
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct ClientBuilder {
+    pub(crate) url: String,
+    pub(crate) http_client: reqwest::Client,
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) default_headers: HeaderMap,
+    pub(crate) retry_policy: RetryPolicy,
+    pub(crate) rate_limiter: Option<RateLimiter>,
+}
+
+impl Default for ClientBuilder {
+    fn default() -> Self {
+        Self {
+            url: "https://api.monzo.com".into(),
+            http_client: reqwest::Client::default(),
+            timeout: None,
+            default_headers: HeaderMap::new(),
+            retry_policy: RetryPolicy::default(),
+            rate_limiter: None,
+        }
+    }
+}
+
+impl ClientBuilder {
+    /// Set the base URL of the API.
+    ///
+    /// Defaults to `https://api.monzo.com`. This is mostly useful for pointing
+    /// the client at a fake server in tests.
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = url.into();
+        self
+    }
+
+    /// Use a custom [`reqwest::Client`], for example to configure a proxy.
+    pub fn http_client(mut self, http_client: reqwest::Client) -> Self {
+        self.http_client = http_client;
+        self
+    }
+
+    /// Set a timeout for each request.
+    ///
+    /// The timeout applies from when the request starts connecting until the
+    /// response body has finished.
+    pub const fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Set the `User-Agent` header sent with each request
+    pub fn user_agent(self, user_agent: HeaderValue) -> Self {
+        self.default_header(reqwest::header::USER_AGENT, user_agent)
+    }
+
+    /// Add a header which is sent with each request
+    pub fn default_header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.default_headers.insert(name, value);
+        self
+    }
+
+    /// Set the policy for retrying requests which fail with transient errors.
+    pub const fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Limit the rate at which the client sends requests.
+    pub fn rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Build a [`Client`] which authenticates using only an access token
+    pub fn build(self, access_token: impl Into<String>) -> Client<Quick> {
+        let inner_client = Quick::new(self, access_token.into());
+        Client { inner_client }
+    }
+
+    /// Build a [`Client`] which can refresh its own access token
+    pub fn build_refreshable(
+        self,
+        access_token: impl Into<String>,
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+        refresh_token: impl Into<String>,
+    ) -> Client<Refreshable> {
+        self.build(access_token)
+            .with_refresh_tokens(client_id, client_secret, refresh_token)
+    }
+
+    /// Build a [`Client`] which can refresh its own access token, using the
+    /// tokens in a [`TokenStore`].
+    ///
+    /// See [`Client::from_token_store`].
+    pub fn build_from_token_store(
+        self,
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+        token_store: impl TokenStore + 'static,
+    ) -> Result<Client<Refreshable>> {
+        Client::from_quick_client_and_token_store(
+            Quick::new(self, String::new()),
+            client_id,
+            client_secret,
+            token_store,
+        )
+    }
+
+    /// Begin an `OAuth2` authorization-code flow.
+    ///
+    /// The resulting [`Client`] is built with this configuration. See
+    /// [`Client::authorize`].
+    pub fn authorize(
+        self,
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+        redirect_uri: impl Into<String>,
+    ) -> auth::Authorization {
+        auth::Authorization::new(client_id, client_secret, redirect_uri).with_client_builder(self)
+    }
+}
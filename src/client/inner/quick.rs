@@ -1,4 +1,8 @@
-use crate::{client, client::Client, endpoints::Endpoint};
+use crate::{
+    client,
+    client::{Client, ClientBuilder, RateLimiter, RetryPolicy},
+    endpoints::Endpoint,
+};
 
 /// A quick and dirty Monzo API client.
 ///
@@ -11,6 +15,10 @@ pub struct Quick {
     http_client: reqwest::Client,
     access_token: String,
     url: String,
+    timeout: Option<std::time::Duration>,
+    default_headers: reqwest::header::HeaderMap,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<RateLimiter>,
 }
 
 impl Client<Quick> {
@@ -19,14 +27,15 @@ impl Client<Quick> {
     /// This `Client` needs only an access token to authenticate against
     /// the Monzo API, but is incapable of refreshing its access if the
     /// token expires.
+    ///
+    /// Use [`Client::builder`] to customise the client.
     pub fn new(access_token: impl Into<String>) -> Self {
-        let http_client = reqwest::Client::default();
-        let inner_client = Quick {
-            http_client,
-            access_token: access_token.into(),
-            url: "https://api.monzo.com".into(),
-        };
-        Self { inner_client }
+        Self::builder().build(access_token)
+    }
+
+    /// Return a [`ClientBuilder`] for configuring a new client.
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::default()
     }
 
     /// Upgrade a Client by adding refresh tokens.
@@ -43,17 +52,40 @@ impl Client<Quick> {
     }
 }
 
-impl client::Inner for Quick {
-    async fn execute<E>(&self, endpoint: &E) -> reqwest::Result<reqwest::Response>
+impl Quick {
+    pub(crate) fn new(builder: ClientBuilder, access_token: String) -> Self {
+        Self {
+            http_client: builder.http_client,
+            access_token,
+            url: builder.url,
+            timeout: builder.timeout,
+            default_headers: builder.default_headers,
+            retry_policy: builder.retry_policy,
+            rate_limiter: builder.rate_limiter,
+        }
+    }
+
+    /// Construct and send an HTTP request, authenticating with the given
+    /// access token if the endpoint requires it.
+    pub(super) async fn execute_with_token<E>(
+        &self,
+        endpoint: &E,
+        access_token: &str,
+    ) -> reqwest::Result<reqwest::Response>
     where
         E: Endpoint,
     {
         let mut request = self
             .http_client
-            .request(E::METHOD, self.url.clone() + endpoint.endpoint());
+            .request(E::METHOD, self.url.clone() + endpoint.endpoint())
+            .headers(self.default_headers.clone());
+
+        if let Some(timeout) = self.timeout {
+            request = request.timeout(timeout);
+        }
 
         if E::AUTH_REQUIRED {
-            request = request.bearer_auth(&self.access_token);
+            request = request.bearer_auth(access_token);
         }
 
         if let Some(query) = endpoint.query() {
@@ -68,11 +100,24 @@ impl client::Inner for Quick {
             request = request.json(json);
         }
 
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire().await;
+        }
+
         request.send().await
     }
+}
+
+impl client::Inner for Quick {
+    async fn execute<E>(&self, endpoint: &E) -> reqwest::Result<reqwest::Response>
+    where
+        E: Endpoint,
+    {
+        self.execute_with_token(endpoint, &self.access_token).await
+    }
 
-    fn access_token(&self) -> &String {
-        &self.access_token
+    fn access_token(&self) -> String {
+        self.access_token.clone()
     }
 
     fn set_access_token(&mut self, access_token: String) {
@@ -82,4 +127,28 @@ impl client::Inner for Quick {
     fn url(&self) -> &str {
         &self.url
     }
+
+    fn http_client(&self) -> &reqwest::Client {
+        &self.http_client
+    }
+
+    fn timeout(&self) -> Option<std::time::Duration> {
+        self.timeout
+    }
+
+    fn retry_policy(&self) -> RetryPolicy {
+        self.retry_policy
+    }
+
+    fn set_retry_policy(&mut self, retry_policy: RetryPolicy) {
+        self.retry_policy = retry_policy;
+    }
+
+    fn rate_limiter(&self) -> Option<&RateLimiter> {
+        self.rate_limiter.as_ref()
+    }
+
+    fn set_rate_limiter(&mut self, rate_limiter: Option<RateLimiter>) {
+        self.rate_limiter = rate_limiter;
+    }
 }
@@ -1,13 +1,30 @@
+use std::sync::{Arc, PoisonError, RwLock};
+
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::de::DeserializeOwned;
+use tokio::sync::Mutex;
+
 use crate::{
     client,
-    client::{Client, Inner},
+    client::{Client, Inner, RateLimiter, RetryPolicy},
     endpoints::{auth, Endpoint},
-    Result,
+    token_store::{TokenStore, Tokens},
+    Error, Result,
 };
 
 /// A full-featured Monzo API client.
 ///
-/// This client can refresh it's own access token if it expires
+/// This client can refresh it's own access token if it expires.
+///
+/// If the expiry time of the access token is known, the client refreshes its
+/// tokens shortly before they expire (see
+/// [`Client::with_refresh_margin`](crate::Client::with_refresh_margin)).
+/// Otherwise, when a request fails because the access token has expired, the
+/// client refreshes its tokens and replays the request. Concurrent requests
+/// share a single refresh. Cloned clients share the same tokens.
+///
+/// New tokens can be persisted with a [`TokenStore`] (see
+/// [`Client::with_token_store`](crate::Client::with_token_store)).
 /// See the individual methods for descriptions of the API endpoints.
 #[derive(Debug, Clone)]
 #[must_use]
@@ -16,26 +33,228 @@ pub struct Refreshable {
 
     client_id: String,
     client_secret: String,
-    refresh_token: String,
+    tokens: Arc<RwLock<Tokens>>,
+    token_store: Option<Arc<dyn TokenStore>>,
+    refresh_margin: TimeDelta,
+
+    // held for the duration of a token refresh, so that concurrent callers
+    // don't each try to use the (single-use) refresh token
+    refresh_lock: Arc<Mutex<()>>,
+}
+
+impl Refreshable {
+    fn tokens(&self) -> std::sync::RwLockReadGuard<'_, Tokens> {
+        self.tokens.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn tokens_mut(&self) -> std::sync::RwLockWriteGuard<'_, Tokens> {
+        self.tokens.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn refresh_token(&self) -> String {
+        self.tokens().refresh_token.clone()
+    }
+
+    fn expires_at(&self) -> Option<DateTime<Utc>> {
+        self.tokens().expires_at
+    }
+
+    /// Whether the access token is known to expire within the refresh margin
+    fn expires_soon(&self) -> bool {
+        self.expires_at()
+            .is_some_and(|expires_at| expires_at - self.refresh_margin <= Utc::now())
+    }
+
+    /// Store newly issued tokens, returning the time (in seconds) until the new
+    /// access token expires.
+    ///
+    /// The tokens are also persisted to the [`TokenStore`], if there is one.
+    /// Saving blocks, so it runs on the blocking thread pool.
+    async fn update_tokens(&self, response: auth::RefreshResponse) -> Result<i64> {
+        let expires_in = response.expires_in;
+
+        // an out-of-range lifetime is treated as an unknown expiry time
+        let expires_at = TimeDelta::try_seconds(expires_in)
+            .and_then(|expires_in| Utc::now().checked_add_signed(expires_in));
+
+        let tokens = Tokens {
+            access_token: response.access_token,
+            refresh_token: response.refresh_token,
+            expires_at,
+        };
+
+        tokens.clone_into(&mut self.tokens_mut());
+
+        if let Some(token_store) = &self.token_store {
+            let token_store = Arc::clone(token_store);
+            match tokio::task::spawn_blocking(move || token_store.save(&tokens)).await {
+                Ok(result) => result?,
+                Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+                Err(e) => return Err(std::io::Error::other(e).into()),
+            }
+        }
+
+        Ok(expires_in)
+    }
+
+    /// Refresh the access and refresh tokens, returning the time (in seconds)
+    /// until the new access token expires.
+    ///
+    /// The caller must hold the refresh lock.
+    async fn refresh(&self) -> Result<i64> {
+        tracing::info!("refreshing access token");
+
+        let refresh_token = self.refresh_token();
+        let response: auth::RefreshResponse = client::send_request(
+            self,
+            &auth::Refresh::new(&self.client_id, &self.client_secret, &refresh_token),
+        )
+        .await?;
+
+        let expires_in = self.update_tokens(response).await?;
+
+        tracing::info!("access token refreshed");
+        Ok(expires_in)
+    }
+
+    /// Refresh the tokens, unless another caller has already replaced the
+    /// given (expiring) access token.
+    async fn refresh_if_unchanged(&self, expired_access_token: &str) -> Result<()> {
+        let _refresh_lock = self.refresh_lock.lock().await;
+
+        if self.tokens().access_token == expired_access_token {
+            self.refresh().await?;
+        } else {
+            tracing::debug!("access token already refreshed by another caller");
+        }
+
+        Ok(())
+    }
 }
 
 impl Client<Refreshable> {
     pub(crate) fn from_quick_client(
-        quick_client: client::inner::Quick,
+        mut quick_client: client::inner::Quick,
         client_id: impl Into<String>,
         client_secret: impl Into<String>,
         refresh_token: impl Into<String>,
+    ) -> Self {
+        // the access token is owned by the refreshable client from here on
+        let access_token = quick_client.access_token();
+        quick_client.set_access_token(String::new());
+
+        let tokens = Tokens {
+            access_token,
+            refresh_token: refresh_token.into(),
+            expires_at: None,
+        };
+
+        Self::from_tokens(quick_client, client_id, client_secret, tokens)
+    }
+
+    fn from_tokens(
+        quick_client: client::inner::Quick,
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+        tokens: Tokens,
     ) -> Self {
         let inner_client = Refreshable {
             quick_client,
             client_id: client_id.into(),
             client_secret: client_secret.into(),
-            refresh_token: refresh_token.into(),
+            tokens: Arc::new(RwLock::new(tokens)),
+            token_store: None,
+            refresh_margin: TimeDelta::minutes(5),
+            refresh_lock: Arc::default(),
         };
 
         Self { inner_client }
     }
 
+    /// Begin an `OAuth2` authorization-code flow.
+    ///
+    /// This is the way to obtain a [`Client`] that can refresh its own access
+    /// token, without having previously obtained tokens elsewhere. See
+    /// [`auth::Authorization`] for details.
+    pub fn authorize(
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+        redirect_uri: impl Into<String>,
+    ) -> auth::Authorization {
+        Client::builder().authorize(client_id, client_secret, redirect_uri)
+    }
+
+    /// Exchange an authorization code for a new access/refresh token pair
+    pub(crate) async fn exchange_authorization_code(
+        quick_client: Client<client::inner::Quick>,
+        client_id: String,
+        client_secret: String,
+        redirect_uri: &str,
+        code: &str,
+    ) -> Result<Self> {
+        let quick_client = quick_client.inner_client;
+
+        let response: auth::RefreshResponse = quick_client
+            .handle_request(&auth::Exchange::new(
+                &client_id,
+                &client_secret,
+                redirect_uri,
+                code,
+            ))
+            .await?;
+
+        let client = Self::from_quick_client(quick_client, client_id, client_secret, "");
+        client.inner_client.update_tokens(response).await?;
+
+        Ok(client)
+    }
+
+    /// Create a new client using the tokens in a [`TokenStore`].
+    ///
+    /// The client saves its tokens to the store whenever they are refreshed.
+    pub fn from_token_store(
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+        token_store: impl TokenStore + 'static,
+    ) -> Result<Self> {
+        Client::builder().build_from_token_store(client_id, client_secret, token_store)
+    }
+
+    pub(crate) fn from_quick_client_and_token_store(
+        quick_client: client::inner::Quick,
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+        token_store: impl TokenStore + 'static,
+    ) -> Result<Self> {
+        let tokens = token_store.load()?;
+
+        let mut client = Self::from_tokens(quick_client, client_id, client_secret, tokens);
+        client.inner_client.token_store = Some(Arc::new(token_store));
+
+        Ok(client)
+    }
+
+    /// Persist this client's tokens to a [`TokenStore`].
+    ///
+    /// The current tokens are saved immediately, and again whenever they are
+    /// refreshed.
+    pub fn with_token_store(mut self, token_store: impl TokenStore + 'static) -> Result<Self> {
+        token_store.save(&self.inner_client.tokens())?;
+        self.inner_client.token_store = Some(Arc::new(token_store));
+
+        Ok(self)
+    }
+
+    /// Set how long before the access token expires the client should
+    /// refresh it.
+    ///
+    /// Defaults to 5 minutes.
+    #[must_use]
+    pub const fn with_refresh_margin(mut self, margin: TimeDelta) -> Self {
+        self.inner_client.refresh_margin = margin;
+        self
+    }
+
     /// Get a reference to the client id
     #[must_use]
     pub const fn client_id(&self) -> &String {
@@ -48,34 +267,30 @@ impl Client<Refreshable> {
         &self.inner_client.client_secret
     }
 
-    /// Get a reference to the refresh token
+    /// Return the current refresh token
     #[must_use]
-    pub const fn refresh_token(&self) -> &String {
-        &self.inner_client.refresh_token
+    pub fn refresh_token(&self) -> String {
+        self.inner_client.refresh_token()
     }
 
-    /// Hit the Monzo auth endpoint and request new access and refresh tokens
-    async fn get_refresh_tokens(&self) -> Result<auth::RefreshResponse> {
-        self.inner_client
-            .handle_request(&auth::Refresh::new(
-                self.client_id(),
-                self.client_secret(),
-                self.refresh_token(),
-            ))
-            .await
+    /// The time at which the current access token expires.
+    ///
+    /// This is `None` if the expiry time is unknown, which is the case for
+    /// tokens which were supplied to the client rather than issued to it.
+    #[must_use]
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        self.inner_client.expires_at()
     }
 
     /// Refresh the access and refresh tokens for this client
     ///
-    /// Returns the time (in seconds) until the token expires
-    pub async fn refresh_auth(&mut self) -> Result<i64> {
-        let response = self.get_refresh_tokens().await?;
-        let expires_in = response.expires_in;
-
-        self.set_access_token(response.access_token);
-        self.inner_client.refresh_token = response.refresh_token;
-
-        Ok(expires_in)
+    /// Returns the time (in seconds) until the token expires.
+    ///
+    /// It's not normally necessary to call this method, since the client will
+    /// refresh its tokens automatically when the access token expires.
+    pub async fn refresh_auth(&self) -> Result<i64> {
+        let _refresh_lock = self.inner_client.refresh_lock.lock().await;
+        self.inner_client.refresh().await
     }
 }
 
@@ -84,18 +299,73 @@ impl client::Inner for Refreshable {
     where
         E: Endpoint,
     {
-        self.quick_client.execute(endpoint).await
+        let access_token = self.access_token();
+        self.quick_client
+            .execute_with_token(endpoint, &access_token)
+            .await
     }
 
-    fn access_token(&self) -> &String {
-        self.quick_client.access_token()
+    fn access_token(&self) -> String {
+        self.tokens().access_token.clone()
     }
 
     fn set_access_token(&mut self, access_token: String) {
-        self.quick_client.set_access_token(access_token);
+        let mut tokens = self.tokens_mut();
+        tokens.access_token = access_token;
+        tokens.expires_at = None;
     }
 
     fn url(&self) -> &str {
         self.quick_client.url()
     }
+
+    fn http_client(&self) -> &reqwest::Client {
+        self.quick_client.http_client()
+    }
+
+    fn timeout(&self) -> Option<std::time::Duration> {
+        self.quick_client.timeout()
+    }
+
+    fn retry_policy(&self) -> RetryPolicy {
+        self.quick_client.retry_policy()
+    }
+
+    fn set_retry_policy(&mut self, retry_policy: RetryPolicy) {
+        self.quick_client.set_retry_policy(retry_policy);
+    }
+
+    fn rate_limiter(&self) -> Option<&RateLimiter> {
+        self.quick_client.rate_limiter()
+    }
+
+    fn set_rate_limiter(&mut self, rate_limiter: Option<RateLimiter>) {
+        self.quick_client.set_rate_limiter(rate_limiter);
+    }
+
+    async fn handle_request<E, R>(&self, endpoint: &E) -> Result<R>
+    where
+        R: DeserializeOwned,
+        E: Endpoint,
+    {
+        let mut access_token = self.access_token();
+
+        if E::AUTH_REQUIRED && self.expires_soon() {
+            tracing::info!("access token is about to expire");
+            if let Err(e) = self.refresh_if_unchanged(&access_token).await {
+                tracing::warn!("unable to refresh access token: {}", e);
+            }
+            access_token = self.access_token();
+        }
+
+        match client::send_request(self, endpoint).await {
+            Err(Error::AuthExpired | Error::BadAccessToken(_)) if E::AUTH_REQUIRED => {
+                tracing::warn!("access token has expired or is invalid");
+            }
+            result => return result,
+        }
+
+        self.refresh_if_unchanged(&access_token).await?;
+        client::send_request(self, endpoint).await
+    }
 }
@@ -0,0 +1,296 @@
+//! Client-side rate limiting
+
+use std::{
+    sync::{Arc, Mutex, PoisonError},
+    time::Duration,
+};
+
+use tokio::time::Instant;
+
+/// A token-bucket rate limiter.
+///
+/// The bucket holds up to `burst` tokens, and is refilled at a steady rate.
+/// Each request takes a token, waiting for one to become available if the
+/// bucket is empty. Waiting requests are served in the order they arrive.
+///
+/// A rate limiter is shared between all clones of a client, and the same
+/// limiter can be shared between several clients.
+///
+/// # Example
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use monzo::{Client, RateLimiter};
+///
+/// let rate_limiter = RateLimiter::builder()
+///     .requests_per(100, Duration::from_secs(60))
+///     .burst(10)
+///     .build()?;
+///
+/// let client = Client::new("ACCESS_TOKEN").with_rate_limiter(rate_limiter.clone());
+///
+/// // ...
+///
+/// let metrics = rate_limiter.metrics();
+/// println!("mean wait: {:?}", metrics.mean_wait());
+/// # Ok::<(), monzo::rate_limit::InvalidRate>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    bucket: Arc<Mutex<Bucket>>,
+}
+
+impl RateLimiter {
+    /// Configure a new [`RateLimiter`]
+    pub const fn builder() -> Builder {
+        Builder {
+            requests: 1,
+            per: Duration::from_secs(1),
+            burst: None,
+        }
+    }
+
+    /// Return a snapshot of how long requests have waited for the limiter
+    #[must_use]
+    pub fn metrics(&self) -> Metrics {
+        self.bucket
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .metrics
+    }
+
+    /// Wait until a request is allowed to proceed
+    pub(crate) async fn acquire(&self) {
+        let wait = self
+            .bucket
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .reserve(Instant::now());
+
+        if !wait.is_zero() {
+            tracing::debug!("rate limited, waiting {:?}", wait);
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+/// Builder for a [`RateLimiter`]
+#[derive(Debug, Clone, Copy)]
+#[must_use]
+pub struct Builder {
+    requests: u32,
+    per: Duration,
+    burst: Option<u32>,
+}
+
+impl Builder {
+    /// Allow `requests` requests per interval, on average.
+    ///
+    /// Defaults to 1 request per second.
+    pub const fn requests_per(mut self, requests: u32, per: Duration) -> Self {
+        self.requests = requests;
+        self.per = per;
+        self
+    }
+
+    /// Set the number of requests which may be sent at once, before rate
+    /// limiting takes effect.
+    ///
+    /// Defaults to the number of requests per interval.
+    pub const fn burst(mut self, burst: u32) -> Self {
+        self.burst = Some(burst);
+        self
+    }
+
+    /// Build the [`RateLimiter`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the interval is zero, since that would allow
+    /// unlimited requests.
+    pub fn build(self) -> Result<RateLimiter, InvalidRate> {
+        if self.per.is_zero() {
+            return Err(InvalidRate);
+        }
+
+        let capacity = f64::from(self.burst.unwrap_or(self.requests).max(1));
+        let refill_rate = f64::from(self.requests.max(1)) / self.per.as_secs_f64();
+
+        let bucket = Bucket {
+            capacity,
+            refill_rate,
+            tokens: capacity,
+            last_refill: Instant::now(),
+            metrics: Metrics::default(),
+        };
+
+        Ok(RateLimiter {
+            bucket: Arc::new(Mutex::new(bucket)),
+        })
+    }
+}
+
+/// An error building a [`RateLimiter`] with a zero interval
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("the rate limit interval must be greater than zero")]
+pub struct InvalidRate;
+
+/// Statistics about how long requests have waited for a [`RateLimiter`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Metrics {
+    /// The total number of requests which have passed through the limiter
+    pub requests: u64,
+
+    /// The number of requests which had to wait
+    pub delayed_requests: u64,
+
+    /// The total time requests have spent waiting
+    pub total_wait: Duration,
+
+    /// The longest time any single request has waited
+    pub max_wait: Duration,
+}
+
+impl Metrics {
+    /// The mean time requests have spent waiting
+    #[must_use]
+    pub fn mean_wait(&self) -> Duration {
+        u32::try_from(self.requests)
+            .ok()
+            .and_then(|requests| self.total_wait.checked_div(requests))
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    capacity: f64,
+    refill_rate: f64,
+    tokens: f64,
+    last_refill: Instant,
+    metrics: Metrics,
+}
+
+impl Bucket {
+    /// Take a token, returning how long to wait before it can be used.
+    ///
+    /// The token count is allowed to go negative, which reserves a place in
+    /// the queue for the caller.
+    fn reserve(&mut self, now: Instant) -> Duration {
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = elapsed
+            .mul_add(self.refill_rate, self.tokens)
+            .min(self.capacity);
+        self.last_refill = now;
+
+        self.tokens -= 1.0;
+
+        let wait = if self.tokens < 0.0 {
+            // a very slow rate can need a wait too long to represent
+            Duration::try_from_secs_f64(-self.tokens / self.refill_rate).unwrap_or(Duration::MAX)
+        } else {
+            Duration::ZERO
+        };
+
+        self.metrics.requests += 1;
+        if !wait.is_zero() {
+            self.metrics.delayed_requests += 1;
+        }
+        self.metrics.total_wait = self.metrics.total_wait.saturating_add(wait);
+        self.metrics.max_wait = self.metrics.max_wait.max(wait);
+
+        wait
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::time::Instant;
+
+    use super::{InvalidRate, RateLimiter};
+
+    #[tokio::test(start_paused = true)]
+    async fn waits_for_tokens() {
+        let rate_limiter = RateLimiter::builder()
+            .requests_per(10, Duration::from_secs(1))
+            .burst(2)
+            .build()
+            .unwrap();
+
+        let start = tokio::time::Instant::now();
+        for _ in 0..4 {
+            rate_limiter.acquire().await;
+        }
+
+        // 2 requests are sent immediately, then 1 every 100 milliseconds
+        assert_eq!(start.elapsed(), Duration::from_millis(200));
+
+        let metrics = rate_limiter.metrics();
+        assert_eq!(metrics.requests, 4);
+        assert_eq!(metrics.delayed_requests, 2);
+        assert_eq!(metrics.max_wait, Duration::from_millis(100));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn shared_between_clones() {
+        let rate_limiter = RateLimiter::builder()
+            .requests_per(1, Duration::from_secs(1))
+            .build()
+            .unwrap();
+        let clone = rate_limiter.clone();
+
+        rate_limiter.acquire().await;
+        clone.acquire().await;
+
+        let metrics = rate_limiter.metrics();
+        assert_eq!(metrics.requests, 2);
+        assert_eq!(metrics.total_wait, Duration::from_secs(1));
+    }
+
+    #[test]
+    fn zero_interval_is_rejected() {
+        let result = RateLimiter::builder()
+            .requests_per(1, Duration::ZERO)
+            .build();
+        assert_eq!(result.unwrap_err(), InvalidRate);
+    }
+
+    #[test]
+    fn very_long_wait_saturates() {
+        let rate_limiter = RateLimiter::builder()
+            .requests_per(1, Duration::MAX)
+            .build()
+            .unwrap();
+
+        let now = Instant::now();
+        let mut bucket = rate_limiter.bucket.lock().unwrap();
+        let waits = [bucket.reserve(now), bucket.reserve(now)];
+        drop(bucket);
+
+        assert_eq!(waits, [Duration::ZERO, Duration::MAX]);
+    }
+
+    #[test]
+    fn saturated_waits_keep_metrics_at_max() {
+        let rate_limiter = RateLimiter::builder()
+            .requests_per(1, Duration::MAX)
+            .build()
+            .unwrap();
+
+        let now = Instant::now();
+        let mut bucket = rate_limiter.bucket.lock().unwrap();
+        for _ in 0..5 {
+            bucket.reserve(now);
+        }
+        drop(bucket);
+
+        let metrics = rate_limiter.metrics();
+        assert_eq!(metrics.requests, 5);
+        assert_eq!(metrics.delayed_requests, 4);
+        assert_eq!(metrics.total_wait, Duration::MAX);
+        assert_eq!(metrics.max_wait, Duration::MAX);
+    }
+}
@@ -0,0 +1,217 @@
+//! Retrying failed requests
+
+use std::time::Duration;
+
+use crate::Error;
+
+/// A policy for retrying requests which fail with transient errors.
+///
+/// Requests are retried if they fail with a server error, a '429 Too Many
+/// Requests' response or a connection error. The delay between attempts grows
+/// exponentially, with random jitter, unless the API sends a `Retry-After`
+/// header. If the API asks the client to wait for longer than the
+/// [maximum backoff](Self::max_backoff), the request isn't retried and
+/// [`Error::TooManyRequests`] is returned instead.
+///
+/// Only requests which can safely be sent more than once are retried. These are
+/// `GET` requests, and requests which carry a dedupe id (such as pot
+/// deposits).
+///
+/// # Example
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use monzo::{Client, RetryPolicy};
+///
+/// let client = Client::new("ACCESS_TOKEN").with_retry_policy(
+///     RetryPolicy::new(5)
+///         .initial_backoff(Duration::from_millis(200))
+///         .max_backoff(Duration::from_secs(10)),
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[must_use]
+pub struct RetryPolicy {
+    max_retries: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    /// Retry up to 3 times, starting with a backoff of 500 milliseconds
+    fn default() -> Self {
+        Self::new(3)
+    }
+}
+
+impl RetryPolicy {
+    /// Create a policy which retries a request up to `max_retries` times
+    pub const fn new(max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+
+    /// Create a policy which never retries requests
+    pub const fn none() -> Self {
+        Self::new(0)
+    }
+
+    /// Set the delay before the first retry.
+    ///
+    /// This is doubled for each subsequent retry.
+    pub const fn initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Set the maximum delay between retries
+    pub const fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// The maximum number of times a request will be retried
+    #[must_use]
+    pub const fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    /// Decide whether a failed request should be retried, and if so how long to
+    /// wait first.
+    ///
+    /// `attempt` is the number of retries which have already been made.
+    pub(crate) fn delay(&self, attempt: u32, error: &Error) -> Option<Duration> {
+        if attempt >= self.max_retries || !is_transient(error) {
+            return None;
+        }
+
+        if let Error::TooManyRequests {
+            retry_after: Some(retry_after),
+        } = error
+        {
+            return (*retry_after <= self.max_backoff).then_some(*retry_after);
+        }
+
+        Some(self.backoff(attempt))
+    }
+
+    /// Exponential backoff with 'full' jitter
+    fn backoff(&self, attempt: u32) -> Duration {
+        use rand::{rng, RngExt};
+
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_backoff);
+
+        backoff.mul_f64(rng().random_range(0.0..=1.0))
+    }
+}
+
+/// Parse the value of a `Retry-After` header
+///
+/// This is either a number of seconds, or an HTTP date. Dates in the past mean
+/// no delay. The obsolete RFC 850 and asctime date formats aren't supported.
+pub fn parse_retry_after(value: &str) -> Option<Duration> {
+    if let Ok(seconds) = value.parse() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let delay = date.signed_duration_since(chrono::Utc::now());
+    Some(delay.to_std().unwrap_or_default())
+}
+
+fn is_transient(error: &Error) -> bool {
+    match error {
+        Error::Server(_) | Error::TooManyRequests { .. } => true,
+        Error::Api(api_error) => api_error.status.is_server_error(),
+        Error::Http(http_error) => http_error.is_connect() || http_error.is_timeout(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use reqwest::StatusCode;
+
+    use super::{parse_retry_after, RetryPolicy};
+    use crate::Error;
+
+    #[test]
+    fn backoff_is_bounded() {
+        let policy = RetryPolicy::new(10)
+            .initial_backoff(Duration::from_millis(100))
+            .max_backoff(Duration::from_secs(1));
+
+        for attempt in 0..10 {
+            let delay = policy
+                .delay(attempt, &Error::Server(StatusCode::BAD_GATEWAY))
+                .unwrap();
+            let bound = Duration::from_millis(100 * 2u64.pow(attempt)).min(Duration::from_secs(1));
+            assert!(delay <= bound);
+        }
+
+        assert!(policy
+            .delay(10, &Error::Server(StatusCode::BAD_GATEWAY))
+            .is_none());
+    }
+
+    #[test]
+    fn honours_retry_after() {
+        let error = Error::TooManyRequests {
+            retry_after: Some(Duration::from_secs(20)),
+        };
+
+        assert_eq!(
+            RetryPolicy::default().delay(0, &error),
+            Some(Duration::from_secs(20))
+        );
+    }
+
+    #[test]
+    fn gives_up_on_long_retry_after() {
+        let error = Error::TooManyRequests {
+            retry_after: Some(Duration::from_secs(86400)),
+        };
+
+        assert!(RetryPolicy::default().delay(0, &error).is_none());
+    }
+
+    #[test]
+    fn retry_after_formats() {
+        assert_eq!(parse_retry_after("120"), Some(Duration::from_secs(120)));
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"),
+            Some(Duration::ZERO)
+        );
+
+        let in_a_minute = (chrono::Utc::now() + chrono::TimeDelta::seconds(60)).to_rfc2822();
+        let delay = parse_retry_after(&in_a_minute).unwrap();
+        assert!(delay > Duration::from_secs(55) && delay <= Duration::from_secs(60));
+
+        assert_eq!(parse_retry_after("soon"), None);
+    }
+
+    #[test]
+    fn ignores_permanent_errors() {
+        let policy = RetryPolicy::default();
+
+        assert!(policy.delay(0, &Error::AuthExpired).is_none());
+        assert!(policy
+            .delay(0, &Error::Client(StatusCode::NOT_FOUND))
+            .is_none());
+    }
+
+    #[test]
+    fn none() {
+        assert!(RetryPolicy::none()
+            .delay(0, &Error::Server(StatusCode::BAD_GATEWAY))
+            .is_none());
+    }
+}
@@ -1,12 +1,15 @@
 use erased_serde::Serialize as ErasedSerialize;
 
 pub mod accounts;
+pub mod attachments;
 pub mod auth;
 pub mod balance;
 pub mod feed_items;
 pub mod pots;
+pub mod receipts;
 pub mod transactions;
 mod utils;
+pub mod webhooks;
 pub mod who_am_i;
 
 pub trait Endpoint: Sync {
@@ -22,4 +25,11 @@ pub trait Endpoint: Sync {
     fn json(&self) -> Option<&dyn ErasedSerialize> {
         None
     }
+
+    /// Whether the request can safely be sent more than once.
+    ///
+    /// Only idempotent requests are retried.
+    fn idempotent(&self) -> bool {
+        Self::METHOD == reqwest::Method::GET
+    }
 }
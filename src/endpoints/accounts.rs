@@ -1,10 +1,12 @@
 //! Accounts API endpoint
 
 use chrono::{DateTime, Utc};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+
+use crate::money::Currency;
 
 /// A struct representing a Monzo Account
-#[derive(Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Hash, Clone)]
 #[non_exhaustive]
 pub struct Account {
     /// The unique ID of the account
@@ -23,8 +25,8 @@ pub struct Account {
     /// The account description
     pub description: String,
 
-    /// This the a three-letter currency code
-    pub currency: String,
+    /// The currency of the account
+    pub currency: Currency,
 
     /// This is a country code for the country where the account is held
     pub country_code: String,
@@ -36,10 +38,64 @@ pub struct Account {
     ///
     /// This is only set for business accounts
     pub business_id: Option<String>,
+
+    /// Any fields which aren't modelled by this struct
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten, deserialize_with = "extra_without_type")]
+    pub extra: crate::Extra,
+}
+
+/// The fields of the flattened [`Type`] aren't consumed when it's
+/// deserialized, so they're also passed to the extra fields.
+///
+/// The [`Type`] is parsed again from the same fields, and whichever fields it
+/// serializes to are removed, so this can't drift from its definition.
+#[cfg(feature = "extra-fields")]
+fn extra_without_type<'de, D>(deserializer: D) -> Result<crate::Extra, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+    use serde_json::Value;
+
+    let mut extra = crate::Extra::deserialize(deserializer)?;
+    let account_type =
+        Type::deserialize(Value::Object(extra.0.clone())).map_err(D::Error::custom)?;
+    if let Value::Object(fields) = serde_json::to_value(account_type).map_err(D::Error::custom)? {
+        for key in fields.keys() {
+            extra.remove(key);
+        }
+    }
+    Ok(extra)
+}
+
+impl Account {
+    /// The account number, if the account has one
+    #[must_use]
+    pub fn account_number(&self) -> Option<&str> {
+        self.account_type
+            .details()
+            .map(|details| details.account_number.as_str())
+    }
+
+    /// The sort code, if the account has one
+    #[must_use]
+    pub fn sort_code(&self) -> Option<&str> {
+        self.account_type
+            .details()
+            .map(|details| details.sort_code.as_str())
+    }
+
+    /// Whether this is a joint account
+    #[must_use]
+    pub const fn is_joint(&self) -> bool {
+        matches!(self.account_type, Type::UkRetailJoint(_))
+    }
 }
 
 /// Struct representating an owner of a Monzo account
-#[derive(Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[non_exhaustive]
 pub struct Owner {
     /// The user ID of the owner
     pub user_id: String,
@@ -49,10 +105,15 @@ pub struct Owner {
 
     /// The preferred first name of the owner
     pub preferred_first_name: String,
+
+    /// Any fields which aren't modelled by this struct
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
 /// Account details including type and banking information
-#[derive(Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Hash, Clone)]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[non_exhaustive]
 pub enum Type {
@@ -75,7 +136,59 @@ pub enum Type {
     UkLoan,
 }
 
-#[derive(Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+impl Type {
+    /// The kind of account, without its banking information
+    #[must_use]
+    pub const fn kind(&self) -> Kind {
+        match self {
+            Self::UkRetail(_) => Kind::UkRetail,
+            Self::UkRetailJoint(_) => Kind::UkRetailJoint,
+            Self::UkBusiness(_) => Kind::UkBusiness,
+            Self::UkRewards => Kind::UkRewards,
+            Self::UkMonzoFlex => Kind::UkMonzoFlex,
+            Self::UkLoan => Kind::UkLoan,
+        }
+    }
+
+    /// The banking information, for the kinds of account which have it
+    #[must_use]
+    pub const fn details(&self) -> Option<&AccountDetails> {
+        match self {
+            Self::UkRetail(details) | Self::UkRetailJoint(details) | Self::UkBusiness(details) => {
+                Some(details)
+            }
+            Self::UkRewards | Self::UkMonzoFlex | Self::UkLoan => None,
+        }
+    }
+}
+
+/// The kind of an account
+///
+/// This is used to filter the [`List`] of accounts by type.
+#[derive(Serialize, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum Kind {
+    /// A standard monzo account
+    UkRetail,
+
+    /// A monzo joint account
+    UkRetailJoint,
+
+    /// A monzo business account
+    UkBusiness,
+
+    /// A monzo rewards account
+    UkRewards,
+
+    /// A monzo flex account
+    UkMonzoFlex,
+
+    /// A monzo loan account
+    UkLoan,
+}
+
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Hash, Clone)]
 /// Banking information
 pub struct AccountDetails {
     /// The account number
@@ -84,28 +197,14 @@ pub struct AccountDetails {
     pub sort_code: String,
 }
 
-pub(crate) use list::Request as List;
-mod list {
-
-    use crate::endpoints::Endpoint;
-
-    /// An object representing a request to the Monzo API for a list of accounts
-    pub struct Request;
-
-    impl Endpoint for Request {
-        const METHOD: reqwest::Method = reqwest::Method::GET;
-
-        fn endpoint(&self) -> &'static str {
-            "/accounts"
-        }
-    }
-}
+mod list;
+pub use list::Request as List;
 
 #[cfg(test)]
 mod tests {
     use test_case::test_case;
 
-    use super::Account;
+    use super::{Account, AccountDetails, Kind, Type};
 
     #[test_case(
         r#"
@@ -232,6 +331,90 @@ mod tests {
         ; "uk_loan"
     )]
     fn parse_account(json_data: &str) {
-        let _account: Account = serde_json::from_str(json_data).unwrap();
+        let account: Account = serde_json::from_str(json_data).unwrap();
+        assert_eq!(
+            account.sort_code().is_some(),
+            account.account_type.details().is_some()
+        );
+    }
+
+    #[test]
+    fn kind() {
+        let account_type = Type::UkRetailJoint(AccountDetails {
+            account_number: "12345678".to_string(),
+            sort_code: "040004".to_string(),
+        });
+        assert_eq!(account_type.kind(), Kind::UkRetailJoint);
+        assert_eq!(
+            serde_json::to_value(account_type.kind()).unwrap(),
+            "uk_retail_joint"
+        );
+        assert_eq!(Type::UkLoan.details(), None);
+    }
+
+    #[cfg(feature = "extra-fields")]
+    #[test]
+    fn extra_fields() {
+        let json_data = r#"{
+            "id": "acc_0000",
+            "closed": false,
+            "created": "2024-01-01T00:00:00.000Z",
+            "description": "user_0000",
+            "type": "uk_retail",
+            "currency": "GBP",
+            "country_code": "GB",
+            "owners": [],
+            "account_number": "12345678",
+            "sort_code": "040004",
+            "product_type": "standard"
+        }"#;
+
+        let account: Account = serde_json::from_str(json_data).unwrap();
+        assert_eq!(account.extra.len(), 1);
+        assert_eq!(account.extra["product_type"], "standard");
+    }
+
+    /// Whatever fields a [`Type`] has, they must not also end up in the extra
+    /// fields, or they'd be written twice when the account is serialized
+    #[cfg(feature = "extra-fields")]
+    #[test]
+    fn extra_fields_exclude_type() {
+        let details = AccountDetails {
+            account_number: "12345678".to_string(),
+            sort_code: "040004".to_string(),
+        };
+        let types = [
+            Type::UkRetail(details.clone()),
+            Type::UkRetailJoint(details.clone()),
+            Type::UkBusiness(details),
+            Type::UkRewards,
+            Type::UkMonzoFlex,
+            Type::UkLoan,
+        ];
+
+        for account_type in types {
+            let mut json = serde_json::json!({
+                "id": "acc_0000",
+                "closed": false,
+                "created": "2024-01-01T00:00:00Z",
+                "description": "user_0000",
+                "currency": "GBP",
+                "country_code": "GB",
+                "owners": [],
+                "business_id": null,
+                "product_type": "standard"
+            });
+            let serde_json::Value::Object(type_fields) =
+                serde_json::to_value(&account_type).unwrap()
+            else {
+                panic!("expected the account type to serialize to an object");
+            };
+            json.as_object_mut().unwrap().extend(type_fields);
+
+            let account: Account = serde_json::from_value(json.clone()).unwrap();
+            assert_eq!(account.account_type, account_type);
+            assert_eq!(account.extra.keys().collect::<Vec<_>>(), ["product_type"]);
+            assert_eq!(serde_json::to_value(&account).unwrap(), json);
+        }
     }
 }
@@ -0,0 +1,98 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Account, Kind};
+use crate::{client, endpoints::Endpoint, Result};
+
+/// A request to retrieve a list of accounts from the Monzo API
+///
+/// Use the builder-style methods to filter the accounts which are returned.
+/// Closed accounts are left out unless
+/// [`include_closed`](Self::include_closed) is set.
+#[derive(Debug)]
+#[must_use]
+pub struct Request<'a, C>
+where
+    C: client::Inner,
+{
+    client: &'a C,
+    query: Query,
+    include_closed: bool,
+    owner: Option<&'a str>,
+}
+
+impl<C> Endpoint for Request<'_, C>
+where
+    C: client::Inner,
+{
+    const METHOD: reqwest::Method = reqwest::Method::GET;
+
+    fn endpoint(&self) -> &'static str {
+        "/accounts"
+    }
+
+    fn query(&self) -> Option<&dyn erased_serde::Serialize> {
+        Some(&self.query)
+    }
+}
+
+impl<'a, C> Request<'a, C>
+where
+    C: client::Inner,
+{
+    pub(crate) const fn new(client: &'a C) -> Self {
+        Self {
+            client,
+            query: Query { account_type: None },
+            include_closed: false,
+            owner: None,
+        }
+    }
+
+    /// Only return accounts of the given type
+    pub const fn account_type(mut self, account_type: Kind) -> Self {
+        self.query.account_type = Some(account_type);
+        self
+    }
+
+    /// Whether to return accounts which have been closed
+    ///
+    /// Defaults to `false`.
+    pub const fn include_closed(mut self, include_closed: bool) -> Self {
+        self.include_closed = include_closed;
+        self
+    }
+
+    /// Only return accounts which are owned (or jointly owned) by the given
+    /// user
+    pub const fn owned_by(mut self, user_id: &'a str) -> Self {
+        self.owner = Some(user_id);
+        self
+    }
+
+    /// Consume the request and return the list of [`Account`]s
+    pub async fn send(self) -> Result<Vec<Account>> {
+        #[derive(Deserialize)]
+        struct Response {
+            accounts: Vec<Account>,
+        }
+
+        let response: Response = self.client.handle_request(&self).await?;
+
+        Ok(response
+            .accounts
+            .into_iter()
+            .filter(|account| self.include_closed || !account.closed)
+            .filter(|account| {
+                self.owner.is_none_or(|user_id| {
+                    account.owners.iter().any(|owner| owner.user_id == user_id)
+                })
+            })
+            .collect())
+    }
+}
+
+#[derive(Serialize, Debug)]
+struct Query {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    account_type: Option<Kind>,
+}
@@ -0,0 +1,75 @@
+//! endpoints for attaching files, such as receipts, to transactions
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+mod upload;
+pub(crate) use upload::Request as Upload;
+mod register;
+pub(crate) use register::Request as Register;
+mod deregister;
+pub(crate) use deregister::Request as Deregister;
+
+/// A file attached to a transaction
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Attachment {
+    /// Unique ID for this attachment
+    pub id: String,
+
+    /// The ID of the user who owns the attachment
+    pub user_id: String,
+
+    /// The ID of the transaction the attachment is registered against
+    pub external_id: String,
+
+    /// The URL of the uploaded file
+    pub file_url: String,
+
+    /// The MIME type of the file, eg. `image/png`
+    pub file_type: String,
+
+    /// The datetime that the attachment was created
+    pub created: DateTime<Utc>,
+
+    /// Any fields which aren't modelled by this struct
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: crate::Extra,
+}
+
+/// A temporary URL which a file can be uploaded to
+///
+/// Returned by
+/// [`Client::attachment_upload_url`](crate::Client::attachment_upload_url).
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct UploadUrl {
+    /// The URL the file will be available at once it has been uploaded
+    pub file_url: String,
+
+    /// The URL to `PUT` the file's contents to
+    pub upload_url: String,
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::Attachment;
+
+    #[test]
+    fn deserialise() {
+        let raw = r#"
+        {
+            "id": "attach_00009238aOAIvVqfb9LrZh",
+            "user_id": "user_00009238aMBIIrS5Rdncq9",
+            "external_id": "tx_00008zIcpb1TB4yeIFXMzx",
+            "file_url": "https://s3-eu-west-1.amazonaws.com/mondo-image-uploads/user_00009237hliZellUicKuG1/LcCu4ogv1xW28OCcvOTL-foo.png",
+            "file_type": "image/png",
+            "created": "2015-11-12T18:37:02Z"
+        }
+        "#;
+
+        serde_json::from_str::<Attachment>(raw).expect("couldn't decode Attachment from json");
+    }
+}
@@ -0,0 +1,32 @@
+use serde::Serialize;
+
+use crate::endpoints::Endpoint;
+
+/// An object representing a request to the Monzo API to remove an attachment
+pub struct Request<'a> {
+    form: Form<'a>,
+}
+
+impl<'a> Request<'a> {
+    pub(crate) const fn new(attachment_id: &'a str) -> Self {
+        let form = Form { id: attachment_id };
+        Self { form }
+    }
+}
+
+impl Endpoint for Request<'_> {
+    const METHOD: reqwest::Method = reqwest::Method::POST;
+
+    fn endpoint(&self) -> &'static str {
+        "/attachment/deregister"
+    }
+
+    fn form(&self) -> Option<&dyn erased_serde::Serialize> {
+        Some(&self.form)
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Form<'a> {
+    id: &'a str,
+}
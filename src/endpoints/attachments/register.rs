@@ -0,0 +1,43 @@
+use serde::Serialize;
+
+use crate::endpoints::Endpoint;
+
+/// An object representing a request to the Monzo API to register an attachment
+/// against a transaction
+pub struct Request<'a> {
+    form: Form<'a>,
+}
+
+impl<'a> Request<'a> {
+    pub(crate) const fn new(
+        transaction_id: &'a str,
+        file_url: &'a str,
+        file_type: &'a str,
+    ) -> Self {
+        let form = Form {
+            external_id: transaction_id,
+            file_url,
+            file_type,
+        };
+        Self { form }
+    }
+}
+
+impl Endpoint for Request<'_> {
+    const METHOD: reqwest::Method = reqwest::Method::POST;
+
+    fn endpoint(&self) -> &'static str {
+        "/attachment/register"
+    }
+
+    fn form(&self) -> Option<&dyn erased_serde::Serialize> {
+        Some(&self.form)
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Form<'a> {
+    external_id: &'a str,
+    file_url: &'a str,
+    file_type: &'a str,
+}
@@ -0,0 +1,39 @@
+use serde::Serialize;
+
+use crate::endpoints::Endpoint;
+
+/// An object representing a request to the Monzo API for an attachment upload
+/// URL
+pub struct Request<'a> {
+    form: Form<'a>,
+}
+
+impl<'a> Request<'a> {
+    pub(crate) const fn new(file_name: &'a str, file_type: &'a str, content_length: u64) -> Self {
+        let form = Form {
+            file_name,
+            file_type,
+            content_length,
+        };
+        Self { form }
+    }
+}
+
+impl Endpoint for Request<'_> {
+    const METHOD: reqwest::Method = reqwest::Method::POST;
+
+    fn endpoint(&self) -> &'static str {
+        "/attachment/upload"
+    }
+
+    fn form(&self) -> Option<&dyn erased_serde::Serialize> {
+        Some(&self.form)
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Form<'a> {
+    file_name: &'a str,
+    file_type: &'a str,
+    content_length: u64,
+}
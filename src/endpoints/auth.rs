@@ -1,7 +1,188 @@
 //! Authentication endpoint
 
+pub use authorization::Authorization;
+pub use exchange::Request as Exchange;
 pub use refresh::{Request as Refresh, Response as RefreshResponse};
 
+mod authorization {
+    use crate::{
+        client::{inner::Refreshable, Client, ClientBuilder},
+        webhooks::verify_secret,
+        Error, Result,
+    };
+
+    const AUTH_URL: &str = "https://auth.monzo.com/";
+
+    /// An in-progress `OAuth2` authorization-code flow.
+    ///
+    /// Send the user to the [`url`](Self::url), and when Monzo redirects them
+    /// back to your redirect URI, pass the returned `code` and `state` query
+    /// parameters to [`exchange`](Self::exchange) to obtain a [`Client`]
+    /// which can refresh its own access token.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use monzo::Client;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let authorization = Client::authorize(
+    ///     "CLIENT_ID",
+    ///     "CLIENT_SECRET",
+    ///     "http://localhost:8080/callback",
+    /// );
+    ///
+    /// println!("visit {} to log in", authorization.url());
+    ///
+    /// // ...and after the user has been redirected back
+    /// # let (code, state) = ("CODE", "STATE");
+    /// let client = authorization.exchange(code, state).await?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[derive(Debug, Clone)]
+    #[must_use]
+    pub struct Authorization {
+        client_builder: ClientBuilder,
+        client_id: String,
+        client_secret: String,
+        redirect_uri: String,
+        state: String,
+    }
+
+    impl Authorization {
+        /// Start a new authorization flow, with a randomly generated `state`
+        /// token.
+        pub fn new(
+            client_id: impl Into<String>,
+            client_secret: impl Into<String>,
+            redirect_uri: impl Into<String>,
+        ) -> Self {
+            use rand::{distr::Alphanumeric, rng, RngExt};
+
+            let state = rng()
+                .sample_iter(&Alphanumeric)
+                .map(char::from)
+                .take(32)
+                .collect();
+
+            Self {
+                client_builder: ClientBuilder::default(),
+                client_id: client_id.into(),
+                client_secret: client_secret.into(),
+                redirect_uri: redirect_uri.into(),
+                state,
+            }
+        }
+
+        pub(crate) fn with_client_builder(mut self, client_builder: ClientBuilder) -> Self {
+            self.client_builder = client_builder;
+            self
+        }
+
+        /// The CSRF token which Monzo will echo back to the redirect URI
+        #[must_use]
+        pub fn state(&self) -> &str {
+            &self.state
+        }
+
+        /// The URL of the Monzo login page that the user should be sent to
+        #[must_use]
+        #[allow(clippy::missing_panics_doc)] // the base URL is a valid constant
+        pub fn url(&self) -> reqwest::Url {
+            reqwest::Url::parse_with_params(
+                AUTH_URL,
+                [
+                    ("client_id", self.client_id.as_str()),
+                    ("redirect_uri", self.redirect_uri.as_str()),
+                    ("response_type", "code"),
+                    ("state", self.state.as_str()),
+                ],
+            )
+            .expect("auth URL is valid")
+        }
+
+        /// Check that the `state` returned to the redirect URI matches the one
+        /// that was sent.
+        ///
+        /// The comparison takes the same time however much of the state
+        /// matches.
+        pub fn validate_state(&self, state: &str) -> Result<()> {
+            if verify_secret(state, &self.state) {
+                Ok(())
+            } else {
+                Err(Error::StateMismatch)
+            }
+        }
+
+        /// Validate the returned `state`, and exchange the authorization
+        /// `code` for access and refresh tokens.
+        pub async fn exchange(self, code: &str, state: &str) -> Result<Client<Refreshable>> {
+            self.validate_state(state)?;
+
+            Client::exchange_authorization_code(
+                self.client_builder.build(""),
+                self.client_id,
+                self.client_secret,
+                &self.redirect_uri,
+                code,
+            )
+            .await
+        }
+    }
+}
+
+mod exchange {
+    use serde::Serialize;
+
+    use crate::endpoints::Endpoint;
+
+    /// A request to exchange an authorization code for authentication tokens.
+    pub struct Request<'a> {
+        form: Form<'a>,
+    }
+
+    impl<'a> Request<'a> {
+        pub(crate) const fn new(
+            client_id: &'a str,
+            client_secret: &'a str,
+            redirect_uri: &'a str,
+            code: &'a str,
+        ) -> Self {
+            let form = Form {
+                grant_type: "authorization_code",
+                client_id,
+                client_secret,
+                redirect_uri,
+                code,
+            };
+            Self { form }
+        }
+    }
+
+    impl Endpoint for Request<'_> {
+        const AUTH_REQUIRED: bool = false;
+        const METHOD: reqwest::Method = reqwest::Method::POST;
+
+        fn endpoint(&self) -> &'static str {
+            "/oauth2/token"
+        }
+
+        fn form(&self) -> Option<&dyn erased_serde::Serialize> {
+            Some(&self.form)
+        }
+    }
+
+    #[derive(Serialize)]
+    struct Form<'a> {
+        grant_type: &'static str,
+        client_id: &'a str,
+        client_secret: &'a str,
+        redirect_uri: &'a str,
+        code: &'a str,
+    }
+}
+
 mod refresh {
 
     use serde::{Deserialize, Serialize};
@@ -84,3 +265,42 @@ mod refresh {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Authorization;
+
+    #[test]
+    fn authorization_url() {
+        let authorization = Authorization::new(
+            "CLIENT_ID",
+            "CLIENT_SECRET",
+            "http://localhost:8080/callback",
+        );
+        let url = authorization.url();
+
+        assert_eq!(url.host_str(), Some("auth.monzo.com"));
+
+        let query: Vec<_> = url.query_pairs().into_owned().collect();
+        assert_eq!(
+            query,
+            vec![
+                ("client_id".into(), "CLIENT_ID".into()),
+                (
+                    "redirect_uri".into(),
+                    "http://localhost:8080/callback".into()
+                ),
+                ("response_type".into(), "code".into()),
+                ("state".into(), authorization.state().into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_state() {
+        let authorization = Authorization::new("CLIENT_ID", "CLIENT_SECRET", "REDIRECT_URI");
+
+        assert!(authorization.validate_state(authorization.state()).is_ok());
+        assert!(authorization.validate_state("forged").is_err());
+    }
+}
@@ -1,24 +1,119 @@
 //! Acount balance
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    endpoints::utils::{empty_as_none, empty_string_as_none, null_as_default},
+    money::{Currency, Money},
+};
 
 /// The balance of a Monzo Account
-#[derive(Deserialize, Debug)]
+///
+/// All amounts are in the minor units of their currency, ie. pence for GBP.
+///
+/// The `/balance` response doesn't include the account's overdraft limit, so
+/// that isn't modelled. [`Balance::is_overdrawn`] is worked out from the
+/// balance alone.
+///
+/// This implements [`PartialEq`] but not [`Eq`], because the
+/// [exchange rate](Self::local_exchange_rate) is a floating point number.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
 #[must_use]
 pub struct Balance {
-    /// The account balance, in the minor units of the listed currency. ie for
-    /// GBP, the balance is in pence.
+    /// The balance available to spend, not including any money in pots
+    ///
+    /// This is negative if the account is overdrawn.
     pub balance: i64,
 
-    /// The total account balance. I haven't figured out what the difference is
-    /// yet
+    /// The available balance plus the combined balance of all of the
+    /// account's pots
     pub total_balance: i64,
 
-    /// three-letter currency code for the account
-    pub currency: String,
+    /// The available balance plus the balance of any instant access savings
+    /// pots, but not other pots
+    #[serde(default)]
+    pub balance_including_flexible_savings: Option<i64>,
+
+    /// The currency of the account
+    pub currency: Currency,
+
+    /// Total expenditure so far this calendar day
+    pub spend_today: i64,
+
+    /// The local currency, if the card has been used abroad recently
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    pub local_currency: Option<Currency>,
+
+    /// The exchange rate from the account's currency to the
+    /// [local currency](Self::local_currency), if there is one
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub local_exchange_rate: Option<f64>,
+
+    /// Expenditure so far this calendar day in each foreign currency
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub local_spend: Vec<LocalSpend>,
+
+    /// Any fields which aren't modelled by this struct
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: crate::Extra,
+}
+
+impl Balance {
+    /// The balance available to spend
+    #[must_use]
+    pub fn balance_money(&self) -> Money {
+        Money::new(self.balance, self.currency.clone())
+    }
+
+    /// The available balance plus the balance of all pots
+    #[must_use]
+    pub fn total_balance_money(&self) -> Money {
+        Money::new(self.total_balance, self.currency.clone())
+    }
+
+    /// The available balance plus the balance of any instant access savings
+    /// pots
+    #[must_use]
+    pub fn balance_including_flexible_savings_money(&self) -> Option<Money> {
+        self.balance_including_flexible_savings
+            .map(|balance| Money::new(balance, self.currency.clone()))
+    }
+
+    /// Total expenditure so far this calendar day
+    #[must_use]
+    pub fn spend_today_money(&self) -> Money {
+        Money::new(self.spend_today, self.currency.clone())
+    }
+
+    /// Whether the account is overdrawn, ie. its available balance is negative
+    ///
+    /// The `/balance` endpoint doesn't report the account's arranged overdraft
+    /// limit, only the balance.
+    #[must_use]
+    pub const fn is_overdrawn(&self) -> bool {
+        self.balance < 0
+    }
+}
 
-    /// total expenditure so far this calendar day
+/// Expenditure in a foreign currency
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct LocalSpend {
+    /// Total expenditure in this currency so far this calendar day
     pub spend_today: i64,
+
+    /// The currency
+    pub currency: Currency,
+}
+
+impl LocalSpend {
+    /// Total expenditure in this currency so far this calendar day
+    #[must_use]
+    pub fn spend_today_money(&self) -> Money {
+        Money::new(self.spend_today, self.currency.clone())
+    }
 }
 
 pub use get::Request as Get;
@@ -28,6 +123,7 @@ mod get {
     use crate::endpoints::Endpoint;
 
     /// An object representing a request to the Monzo API for a list of accounts
+    #[derive(Debug)]
     pub struct Request<'a> {
         query: Query<'a>,
     }
@@ -56,3 +152,64 @@ mod get {
         account_id: &'a str,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Balance;
+    use crate::money::{Currency, Money};
+
+    #[test]
+    fn deserialise() {
+        let raw = r#"
+        {
+            "balance": -1500,
+            "total_balance": 48500,
+            "balance_including_flexible_savings": 23500,
+            "currency": "GBP",
+            "spend_today": -2000,
+            "local_currency": "",
+            "local_exchange_rate": 0,
+            "local_spend": []
+        }
+        "#;
+
+        let balance: Balance = serde_json::from_str(raw).unwrap();
+        assert!(balance.is_overdrawn());
+        assert_eq!(
+            balance.balance_including_flexible_savings_money(),
+            Some(Money::new(23500, Currency::GBP))
+        );
+        assert_eq!(balance.local_currency, None);
+        assert_eq!(balance.local_exchange_rate, None);
+        assert!(balance.local_spend.is_empty());
+    }
+
+    #[test]
+    fn deserialise_abroad() {
+        let raw = r#"
+        {
+            "balance": 10000,
+            "total_balance": 10000,
+            "currency": "GBP",
+            "spend_today": -850,
+            "local_currency": "EUR",
+            "local_exchange_rate": 1.17,
+            "local_spend": [
+                {
+                    "spend_today": -1000,
+                    "currency": "EUR"
+                }
+            ]
+        }
+        "#;
+
+        let balance: Balance = serde_json::from_str(raw).unwrap();
+        assert_eq!(balance.balance_including_flexible_savings, None);
+        assert_eq!(balance.local_currency, Some(Currency::EUR));
+        assert_eq!(balance.local_exchange_rate, Some(1.17));
+        assert_eq!(
+            balance.local_spend[0].spend_today_money(),
+            Money::new(-1000, Currency::EUR)
+        );
+    }
+}
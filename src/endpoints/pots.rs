@@ -1,19 +1,28 @@
 //! endpoints for working with Monzo pots
 
 use chrono::{DateTime, Utc};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-use crate::endpoints::utils::empty_string_as_none;
+use crate::{
+    endpoints::utils::empty_string_as_none,
+    money::{self, Currency, Money},
+};
 
 mod list;
-pub use list::Request as List;
+pub(crate) use list::Request as List;
 mod deposit;
 pub use deposit::Request as Deposit;
 mod withdraw;
 pub use withdraw::Request as Withdraw;
+mod create;
+pub use create::Request as Create;
+mod update;
+pub use update::Request as Update;
+mod delete;
+pub(crate) use delete::Request as Delete;
 
 /// Representation of a Monzo pot
-#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
 #[non_exhaustive]
 pub struct Pot {
     /// Unique ID for this Monzo pot
@@ -29,8 +38,8 @@ pub struct Pot {
     /// The pot balance, in the minor units of the specified currency
     pub balance: i64,
 
-    /// Three letter code for the pot's currency
-    pub currency: String,
+    /// The pot's currency
+    pub currency: Currency,
 
     /// The goal balance for this pot, if set
     #[serde(default)]
@@ -50,6 +59,49 @@ pub struct Pot {
     /// *Note that in future the API will simply not return pots which have been
     /// deleted*
     pub deleted: bool,
+
+    /// Any fields which aren't modelled by this struct
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: crate::Extra,
+}
+
+impl Pot {
+    /// The pot balance
+    #[must_use]
+    pub fn balance_money(&self) -> Money {
+        Money::new(self.balance, self.currency.clone())
+    }
+
+    /// The goal balance for this pot, if set
+    #[must_use]
+    pub fn goal_amount_money(&self) -> Option<Money> {
+        self.goal_amount
+            .map(|goal_amount| Money::new(goal_amount, self.currency.clone()))
+    }
+}
+
+/// Check that an amount can be sent for a pot in the given currency, and
+/// return its minor units
+fn minor_units(amount: &Money, currency: &Currency) -> Result<u64, money::Error> {
+    if amount.currency != *currency {
+        return Err(money::Error::CurrencyMismatch(
+            currency.clone(),
+            amount.currency.clone(),
+        ));
+    }
+    u64::try_from(amount.minor_units).map_err(|_| money::Error::Negative)
+}
+
+/// Generate a random dedupe id for a deposit or withdrawal
+fn random_dedupe_id() -> String {
+    use rand::{distr::Alphanumeric, rng, RngExt};
+
+    rng()
+        .sample_iter(&Alphanumeric)
+        .map(char::from)
+        .take(10)
+        .collect()
 }
 
 #[cfg(test)]
@@ -0,0 +1,99 @@
+use serde::Serialize;
+
+use super::{minor_units, Pot};
+use crate::{
+    client,
+    endpoints::Endpoint,
+    money::{self, Currency, Money},
+    Account, Result,
+};
+
+/// A request to create a new pot
+///
+/// Use the builder-style methods to set optional fields on the request
+#[derive(Debug)]
+#[must_use]
+pub struct Request<'a, C>
+where
+    C: client::Inner,
+{
+    client: &'a C,
+    currency: Currency,
+    form: Form<'a>,
+    invalid_amount: Option<money::Error>,
+}
+
+impl<C> Endpoint for Request<'_, C>
+where
+    C: client::Inner,
+{
+    const METHOD: reqwest::Method = reqwest::Method::POST;
+
+    fn endpoint(&self) -> &'static str {
+        "/pots/create"
+    }
+
+    fn form(&self) -> Option<&dyn erased_serde::Serialize> {
+        Some(&self.form)
+    }
+}
+
+impl<'a, C> Request<'a, C>
+where
+    C: client::Inner,
+{
+    pub(crate) fn new(client: &'a C, current_account: &'a Account, name: &'a str) -> Self {
+        let form = Form {
+            current_account_id: &current_account.id,
+            name,
+            goal_amount: None,
+            style: None,
+        };
+        Self {
+            client,
+            currency: current_account.currency.clone(),
+            form,
+            invalid_amount: None,
+        }
+    }
+
+    /// Set the goal balance of the pot
+    ///
+    /// The goal must be in the account's currency, and can't be negative.
+    pub fn goal_amount(mut self, goal_amount: &Money) -> Self {
+        match minor_units(goal_amount, &self.currency) {
+            Ok(goal_amount) => self.form.goal_amount = Some(goal_amount),
+            Err(e) => self.invalid_amount = Some(e),
+        }
+        self
+    }
+
+    /// Set the built in Monzo image for the pot
+    pub const fn style(mut self, style: &'a str) -> Self {
+        self.form.style = Some(style);
+        self
+    }
+
+    /// Consume the request and return the new [`Pot`]
+    ///
+    /// Returns [`Error::InvalidAmount`](crate::Error::InvalidAmount) without
+    /// sending anything if the goal is invalid.
+    pub async fn send(self) -> Result<Pot> {
+        if let Some(e) = self.invalid_amount {
+            return Err(e.into());
+        }
+        self.client.handle_request(&self).await
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Form<'a> {
+    current_account_id: &'a str,
+    name: &'a str,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    goal_amount: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    style: Option<&'a str>,
+}
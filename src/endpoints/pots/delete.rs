@@ -0,0 +1,21 @@
+use crate::endpoints::Endpoint;
+
+/// An object representing a request to the Monzo API to close a pot
+pub struct Request {
+    endpoint: String,
+}
+
+impl Request {
+    pub(crate) fn new(pot_id: &str) -> Self {
+        let endpoint = format!("/pots/{pot_id}");
+        Self { endpoint }
+    }
+}
+
+impl Endpoint for Request {
+    const METHOD: reqwest::Method = reqwest::Method::DELETE;
+
+    fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+}
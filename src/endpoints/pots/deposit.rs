@@ -1,13 +1,37 @@
 use serde::Serialize;
 
-use crate::endpoints::Endpoint;
+use super::{minor_units, random_dedupe_id, Pot};
+use crate::{
+    client,
+    endpoints::Endpoint,
+    money::{self, Money},
+    Result,
+};
 
-pub struct Request<'a> {
+/// A request to deposit money into a pot
+///
+/// Each request carries a dedupe id, which the Monzo API uses to ignore
+/// repeated requests. A random id is generated by default. To make retries
+/// safe across process restarts, supply your own id with
+/// [`with_dedupe_id`](Self::with_dedupe_id), or derive one from an operation
+/// id with [`with_operation_id`](Self::with_operation_id).
+#[derive(Debug)]
+#[must_use]
+pub struct Request<'a, C>
+where
+    C: client::Inner,
+{
+    client: &'a C,
+    pot_id: &'a str,
     endpoint: String,
     form: Form<'a>,
+    invalid_amount: Option<money::Error>,
 }
 
-impl Endpoint for Request<'_> {
+impl<C> Endpoint for Request<'_, C>
+where
+    C: client::Inner,
+{
     const METHOD: reqwest::Method = reqwest::Method::PUT;
 
     fn endpoint(&self) -> &str {
@@ -17,33 +41,84 @@ impl Endpoint for Request<'_> {
     fn form(&self) -> Option<&dyn erased_serde::Serialize> {
         Some(&self.form)
     }
-}
 
-impl<'a> Request<'a> {
-    pub(crate) fn new(pot_id: &'a str, source_account_id: &'a str, amount: u32) -> Self {
-        use rand::{distr::Alphanumeric, rng, RngExt};
+    // the dedupe id is fixed when the request is created, so it's safe to retry
+    fn idempotent(&self) -> bool {
+        true
+    }
+}
 
-        let endpoint = format!("/pots/{}/deposit", &pot_id);
+impl<'a, C> Request<'a, C>
+where
+    C: client::Inner,
+{
+    pub(crate) fn new(
+        client: &'a C,
+        pot: &'a Pot,
+        source_account_id: &'a str,
+        amount: &Money,
+    ) -> Self {
+        let pot_id = &pot.id;
+        let endpoint = format!("/pots/{pot_id}/deposit");
 
-        let dedupe_id: String = rng()
-            .sample_iter(&Alphanumeric)
-            .map(char::from)
-            .take(10)
-            .collect();
+        let (amount, invalid_amount) = match minor_units(amount, &pot.currency) {
+            Ok(amount) => (amount, None),
+            Err(e) => (0, Some(e)),
+        };
 
         let form = Form {
             source_account_id,
             amount,
-            dedupe_id,
+            dedupe_id: random_dedupe_id(),
         };
 
-        Self { endpoint, form }
+        Self {
+            client,
+            pot_id,
+            endpoint,
+            form,
+            invalid_amount,
+        }
+    }
+
+    /// Use the given dedupe id
+    pub fn with_dedupe_id(mut self, dedupe_id: impl Into<String>) -> Self {
+        self.form.dedupe_id = dedupe_id.into();
+        self
+    }
+
+    /// Derive the dedupe id from an id for this operation, such as a ledger
+    /// entry id
+    ///
+    /// The same operation id always produces the same dedupe id for a given
+    /// pot.
+    pub fn with_operation_id(self, operation_id: &str) -> Self {
+        let dedupe_id = format!("{operation_id}:deposit:{}", self.pot_id);
+        self.with_dedupe_id(dedupe_id)
+    }
+
+    /// The dedupe id which will be sent with the request
+    #[must_use]
+    pub fn dedupe_id(&self) -> &str {
+        &self.form.dedupe_id
+    }
+
+    /// Consume the request and return the updated [`Pot`]
+    ///
+    /// Returns [`Error::InvalidAmount`](crate::Error::InvalidAmount) without
+    /// sending anything if the amount is negative, or isn't in the pot's
+    /// currency.
+    pub async fn send(self) -> Result<Pot> {
+        if let Some(e) = self.invalid_amount {
+            return Err(e.into());
+        }
+        self.client.handle_request(&self).await
     }
 }
 
 #[derive(Debug, Serialize)]
 struct Form<'a> {
     source_account_id: &'a str,
-    amount: u32,
+    amount: u64,
     dedupe_id: String,
 }
@@ -0,0 +1,107 @@
+use serde::Serialize;
+
+use super::{minor_units, Pot};
+use crate::{
+    client,
+    endpoints::Endpoint,
+    money::{self, Currency, Money},
+    Result,
+};
+
+/// A request to update the name, goal or style of a pot
+///
+/// Only the fields which are set are changed
+#[derive(Debug)]
+#[must_use]
+pub struct Request<'a, C>
+where
+    C: client::Inner,
+{
+    client: &'a C,
+    endpoint: String,
+    currency: Currency,
+    form: Form<'a>,
+    invalid_amount: Option<money::Error>,
+}
+
+impl<C> Endpoint for Request<'_, C>
+where
+    C: client::Inner,
+{
+    const METHOD: reqwest::Method = reqwest::Method::PATCH;
+
+    fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    fn form(&self) -> Option<&dyn erased_serde::Serialize> {
+        Some(&self.form)
+    }
+}
+
+impl<'a, C> Request<'a, C>
+where
+    C: client::Inner,
+{
+    pub(crate) fn new(client: &'a C, pot: &Pot) -> Self {
+        let endpoint = format!("/pots/{}", pot.id);
+        let form = Form {
+            name: None,
+            goal_amount: None,
+            style: None,
+        };
+        Self {
+            client,
+            endpoint,
+            currency: pot.currency.clone(),
+            form,
+            invalid_amount: None,
+        }
+    }
+
+    /// Rename the pot
+    pub const fn name(mut self, name: &'a str) -> Self {
+        self.form.name = Some(name);
+        self
+    }
+
+    /// Set the goal balance of the pot
+    ///
+    /// The goal must be in the pot's currency, and can't be negative.
+    pub fn goal_amount(mut self, goal_amount: &Money) -> Self {
+        match minor_units(goal_amount, &self.currency) {
+            Ok(goal_amount) => self.form.goal_amount = Some(goal_amount),
+            Err(e) => self.invalid_amount = Some(e),
+        }
+        self
+    }
+
+    /// Set the built in Monzo image for the pot
+    pub const fn style(mut self, style: &'a str) -> Self {
+        self.form.style = Some(style);
+        self
+    }
+
+    /// Consume the request and return the updated [`Pot`]
+    ///
+    /// Returns [`Error::InvalidAmount`](crate::Error::InvalidAmount) without
+    /// sending anything if the goal is invalid.
+    pub async fn send(self) -> Result<Pot> {
+        if let Some(e) = self.invalid_amount {
+            return Err(e.into());
+        }
+        self.client.handle_request(&self).await
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Form<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<&'a str>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    goal_amount: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    style: Option<&'a str>,
+}
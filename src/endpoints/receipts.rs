@@ -0,0 +1,811 @@
+//! endpoints for adding itemised receipts to transactions
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    money::{Currency, Money},
+    Transaction,
+};
+
+mod put;
+pub(crate) use put::Request as Put;
+mod get;
+pub(crate) use get::Request as Get;
+mod delete;
+pub(crate) use delete::Request as Delete;
+
+/// An itemised receipt for a transaction
+///
+/// Use [`Receipt::for_transaction`] to create a receipt, and
+/// [`Client::upload_receipt`](crate::Client::upload_receipt) to attach it to a
+/// transaction.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Receipt {
+    /// The ID of the transaction the receipt belongs to
+    pub transaction_id: String,
+
+    /// A unique ID for the receipt, chosen by the caller
+    ///
+    /// Uploading a receipt with the same external ID replaces the existing
+    /// receipt.
+    pub external_id: String,
+
+    /// The total amount of the receipt, in the minor units of the currency
+    pub total: i64,
+
+    /// The receipt's currency
+    pub currency: Currency,
+
+    /// The items which were purchased
+    #[serde(default)]
+    pub items: Vec<Item>,
+
+    /// The taxes which were paid
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub taxes: Vec<Tax>,
+
+    /// How the receipt was paid
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub payments: Vec<Payment>,
+
+    /// Details of the merchant
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub merchant: Option<Merchant>,
+}
+
+impl Receipt {
+    /// Start building a receipt for a transaction
+    ///
+    /// The receipt's total is the amount of the transaction, so the receipt
+    /// only builds if its items add up to what was actually spent.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use monzo::{receipts::Item, Client, Currency, Money, Receipt};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = Client::new("ACCESS_TOKEN");
+    ///
+    /// let transaction = client.transaction("TRANSACTION_ID").send().await?;
+    ///
+    /// let receipt = Receipt::for_transaction(&transaction, "RECEIPT_ID")
+    ///     .item(Item::new("Coffee", Money::new(300, Currency::GBP)))
+    ///     .item(Item::new("Croissant", Money::new(250, Currency::GBP)))
+    ///     .build()?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn for_transaction(transaction: &Transaction, external_id: impl Into<String>) -> Builder {
+        // spending is negative, but receipt totals are positive
+        let total = Money::new(
+            transaction.amount.saturating_abs(),
+            transaction.currency.clone(),
+        );
+        Self::builder(&transaction.id, external_id, total)
+    }
+
+    /// Start building a receipt for a transaction, given its ID and amount
+    ///
+    /// Prefer [`Receipt::for_transaction`], which takes the total from the
+    /// transaction itself.
+    ///
+    /// # Example
+    /// ```
+    /// use monzo::{
+    ///     receipts::{Item, Receipt},
+    ///     Currency, Money,
+    /// };
+    ///
+    /// let receipt = Receipt::builder(
+    ///     "TRANSACTION_ID",
+    ///     "RECEIPT_ID",
+    ///     Money::new(550, Currency::GBP),
+    /// )
+    /// .item(Item::new("Coffee", Money::new(300, Currency::GBP)))
+    /// .item(Item::new("Croissant", Money::new(250, Currency::GBP)))
+    /// .build()?;
+    /// # Ok::<(), monzo::receipts::InvalidReceipt>(())
+    /// ```
+    pub fn builder(
+        transaction_id: impl Into<String>,
+        external_id: impl Into<String>,
+        total: Money,
+    ) -> Builder {
+        Builder {
+            receipt: Self {
+                transaction_id: transaction_id.into(),
+                external_id: external_id.into(),
+                total: total.minor_units,
+                currency: total.currency,
+                items: Vec::new(),
+                taxes: Vec::new(),
+                payments: Vec::new(),
+                merchant: None,
+            },
+        }
+    }
+}
+
+/// A builder for a [`Receipt`]
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct Builder {
+    receipt: Receipt,
+}
+
+impl Receipt {
+    /// The total amount of the receipt
+    #[must_use]
+    pub fn total_money(&self) -> Money {
+        Money::new(self.total, self.currency.clone())
+    }
+}
+
+impl Builder {
+    /// Add an item to the receipt
+    pub fn item(mut self, item: Item) -> Self {
+        self.receipt.items.push(item);
+        self
+    }
+
+    /// Add a tax to the receipt
+    pub fn tax(mut self, tax: Tax) -> Self {
+        self.receipt.taxes.push(tax);
+        self
+    }
+
+    /// Add a payment to the receipt
+    pub fn payment(mut self, payment: Payment) -> Self {
+        self.receipt.payments.push(payment);
+        self
+    }
+
+    /// Set the merchant details
+    pub fn merchant(mut self, merchant: Merchant) -> Self {
+        self.receipt.merchant = Some(merchant);
+        self
+    }
+
+    /// Check the receipt adds up, and build it
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if
+    /// - any item, tax or payment is in a different currency to the receipt
+    /// - the items don't add up to the receipt's total
+    /// - any item's sub-items don't add up to the item's amount
+    /// - the taxes add up to more than the total
+    /// - there are payments, and they don't add up to the total
+    /// - any of the sums overflow
+    pub fn build(self) -> Result<Receipt, InvalidReceipt> {
+        let receipt = self.receipt;
+
+        let currencies = receipt
+            .items
+            .iter()
+            .flat_map(Item::currencies)
+            .chain(receipt.taxes.iter().map(|tax| &tax.currency))
+            .chain(receipt.payments.iter().map(|payment| &payment.currency));
+        for currency in currencies {
+            if *currency != receipt.currency {
+                return Err(InvalidReceipt::CurrencyMismatch {
+                    expected: receipt.currency,
+                    found: currency.clone(),
+                });
+            }
+        }
+
+        let items = checked_sum(receipt.items.iter().map(|item| item.amount))?;
+        if items != receipt.total {
+            return Err(InvalidReceipt::TotalMismatch {
+                items,
+                total: receipt.total,
+            });
+        }
+
+        for item in &receipt.items {
+            item.check_sub_items()?;
+        }
+
+        let taxes = checked_sum(receipt.taxes.iter().map(|tax| tax.amount))?;
+        if taxes > receipt.total {
+            return Err(InvalidReceipt::TaxExceedsTotal {
+                taxes,
+                total: receipt.total,
+            });
+        }
+
+        let payments = checked_sum(receipt.payments.iter().map(|payment| payment.amount))?;
+        if !receipt.payments.is_empty() && payments != receipt.total {
+            return Err(InvalidReceipt::PaymentMismatch {
+                payments,
+                total: receipt.total,
+            });
+        }
+
+        Ok(receipt)
+    }
+}
+
+fn checked_sum(amounts: impl IntoIterator<Item = i64>) -> Result<i64, InvalidReceipt> {
+    amounts
+        .into_iter()
+        .try_fold(0_i64, i64::checked_add)
+        .ok_or(InvalidReceipt::Overflow)
+}
+
+/// The reason a [`Receipt`] failed validation
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum InvalidReceipt {
+    /// The items don't add up to the receipt's total
+    #[error("receipt items add up to {items}, but the total is {total}")]
+    TotalMismatch {
+        /// The sum of the item amounts
+        items: i64,
+        /// The receipt's total
+        total: i64,
+    },
+
+    /// The taxes add up to more than the receipt's total
+    #[error("receipt taxes add up to {taxes}, but the total is only {total}")]
+    TaxExceedsTotal {
+        /// The sum of the tax amounts
+        taxes: i64,
+        /// The receipt's total
+        total: i64,
+    },
+
+    /// The payments don't add up to the receipt's total
+    #[error("receipt payments add up to {payments}, but the total is {total}")]
+    PaymentMismatch {
+        /// The sum of the payment amounts
+        payments: i64,
+        /// The receipt's total
+        total: i64,
+    },
+
+    /// An item's sub-items don't add up to the item's amount
+    #[error("sub-items add up to {sub_items}, but the item's amount is {item}")]
+    SubItemMismatch {
+        /// The sum of the sub-item amounts
+        sub_items: i64,
+        /// The amount of the item
+        item: i64,
+    },
+
+    /// An item, tax or payment is in a different currency to the receipt
+    #[error("receipt is in {expected}, but contains an amount in {found}")]
+    CurrencyMismatch {
+        /// The receipt's currency
+        expected: Currency,
+        /// The currency of the item, tax or payment
+        found: Currency,
+    },
+
+    /// An item's tax is negative
+    #[error("item tax can't be negative, but is {tax}")]
+    NegativeTax {
+        /// The item's tax
+        tax: i64,
+    },
+
+    /// The amounts are too large to add up
+    #[error("receipt amounts overflow")]
+    Overflow,
+}
+
+/// A line item on a [`Receipt`]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Item {
+    /// A description of the item
+    pub description: String,
+
+    /// The number of units purchased
+    pub quantity: f64,
+
+    /// The unit of the quantity, eg. `kg`
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub unit: String,
+
+    /// The total amount of the line, in the minor units of the currency
+    pub amount: i64,
+
+    /// The item's currency
+    pub currency: Currency,
+
+    /// The tax included in the amount, in the minor units of the currency
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tax: Option<i64>,
+
+    /// A breakdown of the item, such as a meal deal's components
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sub_items: Vec<Self>,
+}
+
+impl Item {
+    /// The total amount of the line
+    #[must_use]
+    pub fn amount_money(&self) -> Money {
+        Money::new(self.amount, self.currency.clone())
+    }
+
+    /// Create a new item with a quantity of 1
+    #[must_use]
+    pub fn new(description: impl Into<String>, amount: Money) -> Self {
+        Self {
+            description: description.into(),
+            quantity: 1.0,
+            unit: String::new(),
+            amount: amount.minor_units,
+            currency: amount.currency,
+            tax: None,
+            sub_items: Vec::new(),
+        }
+    }
+
+    /// Set the quantity, and optionally the unit of the quantity
+    #[must_use]
+    pub fn quantity(mut self, quantity: f64, unit: impl Into<String>) -> Self {
+        self.quantity = quantity;
+        self.unit = unit.into();
+        self
+    }
+
+    /// Set the tax included in the amount
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tax is negative, or isn't in the item's
+    /// currency.
+    pub fn tax(mut self, tax: &Money) -> Result<Self, InvalidReceipt> {
+        if tax.currency != self.currency {
+            return Err(InvalidReceipt::CurrencyMismatch {
+                expected: self.currency,
+                found: tax.currency.clone(),
+            });
+        }
+        if tax.is_negative() {
+            return Err(InvalidReceipt::NegativeTax {
+                tax: tax.minor_units,
+            });
+        }
+        self.tax = Some(tax.minor_units);
+        Ok(self)
+    }
+
+    /// Add a sub-item
+    ///
+    /// The amounts of an item's sub-items must add up to the item's amount.
+    #[must_use]
+    pub fn sub_item(mut self, item: Self) -> Self {
+        self.sub_items.push(item);
+        self
+    }
+
+    /// The currencies of the item and all of its sub-items
+    fn currencies(&self) -> Vec<&Currency> {
+        std::iter::once(&self.currency)
+            .chain(self.sub_items.iter().flat_map(Self::currencies))
+            .collect()
+    }
+
+    /// Check the sub-items of the item, and all of its sub-items, add up
+    fn check_sub_items(&self) -> Result<(), InvalidReceipt> {
+        if self.sub_items.is_empty() {
+            return Ok(());
+        }
+
+        let sub_items = checked_sum(self.sub_items.iter().map(|item| item.amount))?;
+        if sub_items != self.amount {
+            return Err(InvalidReceipt::SubItemMismatch {
+                sub_items,
+                item: self.amount,
+            });
+        }
+
+        self.sub_items.iter().try_for_each(Self::check_sub_items)
+    }
+}
+
+/// A tax paid on a [`Receipt`], such as VAT
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Tax {
+    /// A description of the tax, eg. `VAT`
+    pub description: String,
+
+    /// The amount of tax, in the minor units of the currency
+    pub amount: i64,
+
+    /// The tax's currency
+    pub currency: Currency,
+
+    /// The merchant's tax registration number
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tax_number: Option<String>,
+}
+
+impl Tax {
+    /// Create a new tax
+    #[must_use]
+    pub fn new(description: impl Into<String>, amount: Money) -> Self {
+        Self {
+            description: description.into(),
+            amount: amount.minor_units,
+            currency: amount.currency,
+            tax_number: None,
+        }
+    }
+
+    /// Set the merchant's tax registration number
+    #[must_use]
+    pub fn tax_number(mut self, tax_number: impl Into<String>) -> Self {
+        self.tax_number = Some(tax_number.into());
+        self
+    }
+}
+
+/// A payment made against a [`Receipt`]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Payment {
+    /// How the payment was made
+    #[serde(rename = "type")]
+    pub kind: PaymentKind,
+
+    /// The amount paid, in the minor units of the currency
+    pub amount: i64,
+
+    /// The payment's currency
+    pub currency: Currency,
+
+    /// The last four digits of the card, for card payments
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_four: Option<String>,
+
+    /// The type of gift card, for gift card payments
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gift_card_type: Option<String>,
+}
+
+impl Payment {
+    /// Create a new payment
+    #[must_use]
+    pub fn new(kind: PaymentKind, amount: Money) -> Self {
+        Self {
+            kind,
+            amount: amount.minor_units,
+            currency: amount.currency,
+            last_four: None,
+            gift_card_type: None,
+        }
+    }
+
+    /// Set the last four digits of the card
+    #[must_use]
+    pub fn last_four(mut self, last_four: impl Into<String>) -> Self {
+        self.last_four = Some(last_four.into());
+        self
+    }
+
+    /// Set the type of gift card
+    #[must_use]
+    pub fn gift_card_type(mut self, gift_card_type: impl Into<String>) -> Self {
+        self.gift_card_type = Some(gift_card_type.into());
+        self
+    }
+}
+
+/// The ways a [`Payment`] can be made
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum PaymentKind {
+    /// Paid by card
+    Card,
+
+    /// Paid in cash
+    Cash,
+
+    /// Paid with a gift card
+    GiftCard,
+}
+
+/// The merchant who issued a [`Receipt`]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Merchant {
+    /// The merchant's name
+    pub name: String,
+
+    /// Whether the purchase was made online
+    #[serde(default)]
+    pub online: bool,
+
+    /// The merchant's phone number
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phone: Option<String>,
+
+    /// The merchant's email address
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+
+    /// The name of the store
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub store_name: Option<String>,
+
+    /// The address of the store
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub store_address: Option<String>,
+
+    /// The postcode of the store
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub store_postcode: Option<String>,
+}
+
+impl Merchant {
+    /// Create a new merchant
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            online: false,
+            phone: None,
+            email: None,
+            store_name: None,
+            store_address: None,
+            store_postcode: None,
+        }
+    }
+
+    /// Set whether the purchase was made online
+    #[must_use]
+    pub const fn online(mut self, online: bool) -> Self {
+        self.online = online;
+        self
+    }
+
+    /// Set the merchant's phone number
+    #[must_use]
+    pub fn phone(mut self, phone: impl Into<String>) -> Self {
+        self.phone = Some(phone.into());
+        self
+    }
+
+    /// Set the merchant's email address
+    #[must_use]
+    pub fn email(mut self, email: impl Into<String>) -> Self {
+        self.email = Some(email.into());
+        self
+    }
+
+    /// Set the name, address and postcode of the store
+    #[must_use]
+    pub fn store(
+        mut self,
+        name: impl Into<String>,
+        address: impl Into<String>,
+        postcode: impl Into<String>,
+    ) -> Self {
+        self.store_name = Some(name.into());
+        self.store_address = Some(address.into());
+        self.store_postcode = Some(postcode.into());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{InvalidReceipt, Item, Payment, PaymentKind, Receipt, Tax};
+    use crate::money::{Currency, Money};
+
+    #[test]
+    fn deserialise() {
+        let raw = r#"
+        {
+            "transaction_id": "tx_00009ZWHgMFsG6TOD54Vfn",
+            "external_id": "test-receipt-1",
+            "total": 1299,
+            "currency": "GBP",
+            "items": [
+                {
+                    "description": "Bananas",
+                    "quantity": 0.5,
+                    "unit": "kg",
+                    "amount": 99,
+                    "currency": "GBP",
+                    "tax": 0
+                },
+                {
+                    "description": "Meal deal",
+                    "quantity": 1,
+                    "amount": 1200,
+                    "currency": "GBP",
+                    "sub_items": [
+                        {
+                            "description": "Sandwich",
+                            "quantity": 1,
+                            "amount": 600,
+                            "currency": "GBP"
+                        }
+                    ]
+                }
+            ],
+            "taxes": [
+                {
+                    "description": "VAT",
+                    "amount": 200,
+                    "currency": "GBP",
+                    "tax_number": "945719291"
+                }
+            ],
+            "payments": [
+                {
+                    "type": "card",
+                    "amount": 1299,
+                    "currency": "GBP",
+                    "last_four": "4242"
+                }
+            ],
+            "merchant": {
+                "name": "Corner Shop",
+                "online": false,
+                "store_address": "1 High Street",
+                "store_postcode": "AB1 2CD"
+            }
+        }
+        "#;
+
+        serde_json::from_str::<Receipt>(raw).expect("couldn't decode Receipt from json");
+    }
+
+    #[test]
+    fn total_mismatch() {
+        let error = Receipt::builder("tx_0000", "receipt_0000", Money::new(500, Currency::GBP))
+            .item(Item::new("Coffee", Money::new(300, Currency::GBP)))
+            .build()
+            .unwrap_err();
+
+        assert_eq!(
+            error,
+            InvalidReceipt::TotalMismatch {
+                items: 300,
+                total: 500
+            }
+        );
+    }
+
+    #[test]
+    fn currency_mismatch() {
+        let error = Receipt::builder("tx_0000", "receipt_0000", Money::new(300, Currency::GBP))
+            .item(Item::new("Coffee", Money::new(300, Currency::EUR)))
+            .build()
+            .unwrap_err();
+
+        assert_eq!(
+            error,
+            InvalidReceipt::CurrencyMismatch {
+                expected: Currency::GBP,
+                found: Currency::EUR
+            }
+        );
+    }
+
+    #[test]
+    fn sub_item_currency_mismatch() {
+        let error = Receipt::builder("tx_0000", "receipt_0000", Money::new(300, Currency::GBP))
+            .item(
+                Item::new("Meal deal", Money::new(300, Currency::GBP))
+                    .sub_item(Item::new("Sandwich", Money::new(200, Currency::EUR))),
+            )
+            .build()
+            .unwrap_err();
+
+        assert_eq!(
+            error,
+            InvalidReceipt::CurrencyMismatch {
+                expected: Currency::GBP,
+                found: Currency::EUR
+            }
+        );
+    }
+
+    #[test]
+    fn sub_item_mismatch() {
+        let receipt = |sandwich, bread| {
+            Receipt::builder("tx_0000", "receipt_0000", Money::new(300, Currency::GBP))
+                .item(
+                    Item::new("Meal deal", Money::new(300, Currency::GBP))
+                        .sub_item(
+                            Item::new("Sandwich", Money::new(sandwich, Currency::GBP))
+                                .sub_item(Item::new("Bread", Money::new(bread, Currency::GBP))),
+                        )
+                        .sub_item(Item::new("Drink", Money::new(100, Currency::GBP))),
+                )
+                .build()
+        };
+
+        assert!(receipt(200, 200).is_ok());
+        assert_eq!(
+            receipt(150, 150).unwrap_err(),
+            InvalidReceipt::SubItemMismatch {
+                sub_items: 250,
+                item: 300
+            }
+        );
+        assert_eq!(
+            receipt(200, 150).unwrap_err(),
+            InvalidReceipt::SubItemMismatch {
+                sub_items: 150,
+                item: 200
+            }
+        );
+    }
+
+    #[test]
+    fn taxes_and_payments() {
+        let receipt = |tax, payment| {
+            Receipt::builder("tx_0000", "receipt_0000", Money::new(300, Currency::GBP))
+                .item(Item::new("Coffee", Money::new(300, Currency::GBP)))
+                .tax(Tax::new("VAT", Money::new(tax, Currency::GBP)))
+                .payment(Payment::new(
+                    PaymentKind::Card,
+                    Money::new(payment, Currency::GBP),
+                ))
+                .build()
+        };
+
+        assert!(receipt(50, 300).is_ok());
+        assert_eq!(
+            receipt(400, 300).unwrap_err(),
+            InvalidReceipt::TaxExceedsTotal {
+                taxes: 400,
+                total: 300
+            }
+        );
+        assert_eq!(
+            receipt(50, 200).unwrap_err(),
+            InvalidReceipt::PaymentMismatch {
+                payments: 200,
+                total: 300
+            }
+        );
+    }
+
+    #[test]
+    fn overflow() {
+        let error = Receipt::builder("tx_0000", "receipt_0000", Money::new(300, Currency::GBP))
+            .item(Item::new("Coffee", Money::new(i64::MAX, Currency::GBP)))
+            .item(Item::new("Cake", Money::new(i64::MAX, Currency::GBP)))
+            .build()
+            .unwrap_err();
+
+        assert_eq!(error, InvalidReceipt::Overflow);
+    }
+
+    #[test]
+    fn item_tax() {
+        let item = Item::new("Coffee", Money::new(300, Currency::GBP));
+
+        let taxed = item.clone().tax(&Money::new(50, Currency::GBP)).unwrap();
+        assert_eq!(taxed.tax, Some(50));
+
+        assert_eq!(
+            item.clone()
+                .tax(&Money::new(50, Currency::EUR))
+                .unwrap_err(),
+            InvalidReceipt::CurrencyMismatch {
+                expected: Currency::GBP,
+                found: Currency::EUR
+            }
+        );
+        assert_eq!(
+            item.tax(&Money::new(-50, Currency::GBP)).unwrap_err(),
+            InvalidReceipt::NegativeTax { tax: -50 }
+        );
+    }
+}
@@ -0,0 +1,32 @@
+use serde::Serialize;
+
+use crate::endpoints::Endpoint;
+
+/// An object representing a request to the Monzo API for a receipt
+pub struct Request<'a> {
+    query: Query<'a>,
+}
+
+impl<'a> Request<'a> {
+    pub(crate) const fn new(external_id: &'a str) -> Self {
+        let query = Query { external_id };
+        Self { query }
+    }
+}
+
+impl Endpoint for Request<'_> {
+    const METHOD: reqwest::Method = reqwest::Method::GET;
+
+    fn endpoint(&self) -> &'static str {
+        "/transaction-receipts"
+    }
+
+    fn query(&self) -> Option<&dyn erased_serde::Serialize> {
+        Some(&self.query)
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Query<'a> {
+    external_id: &'a str,
+}
@@ -0,0 +1,31 @@
+use super::Receipt;
+use crate::endpoints::Endpoint;
+
+/// An object representing a request to the Monzo API to create or replace a
+/// receipt
+pub struct Request<'a> {
+    receipt: &'a Receipt,
+}
+
+impl<'a> Request<'a> {
+    pub(crate) const fn new(receipt: &'a Receipt) -> Self {
+        Self { receipt }
+    }
+}
+
+impl Endpoint for Request<'_> {
+    const METHOD: reqwest::Method = reqwest::Method::PUT;
+
+    fn endpoint(&self) -> &'static str {
+        "/transaction-receipts"
+    }
+
+    fn json(&self) -> Option<&dyn erased_serde::Serialize> {
+        Some(self.receipt)
+    }
+
+    // receipts are keyed by their external id, so uploading twice is harmless
+    fn idempotent(&self) -> bool {
+        true
+    }
+}
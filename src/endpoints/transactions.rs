@@ -5,17 +5,27 @@ use std::collections::HashMap;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::endpoints::utils::empty_string_as_none;
+use crate::{
+    endpoints::{
+        attachments::Attachment,
+        utils::{empty_as_none, empty_string_as_none, null_as_default, string_enum},
+    },
+    money::{Currency, Money},
+};
 
 mod list;
 pub use list::Request as List;
 mod get;
 pub use get::Request as Get;
+mod annotate;
+pub use annotate::Request as Annotate;
+mod category;
+pub use category::Category;
 
 /// A Monzo transaction
 #[allow(clippy::struct_excessive_bools)]
 #[non_exhaustive]
-#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct Transaction {
     /// The unique ID of the account associated with the transaction
     pub account_id: String,
@@ -27,6 +37,10 @@ pub struct Transaction {
     /// Whether the transaction is pending, or complete
     pub amount_is_pending: bool,
 
+    /// Files, such as receipts, which have been attached to the transaction
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub attachments: Vec<Attachment>,
+
     /// Whether the transaction can be added to a tab
     pub can_add_to_tab: bool,
 
@@ -40,13 +54,20 @@ pub struct Transaction {
     pub can_split_the_bill: bool,
 
     /// The transaction category
-    pub category: String,
+    pub category: Category,
+
+    /// How the transaction is split between categories, in the minor units of
+    /// the currency
+    ///
+    /// This is empty unless the transaction has been split.
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub categories: HashMap<Category, i64>,
 
     /// The timestamp when the transaction was created
     pub created: DateTime<Utc>,
 
-    /// The three-letter currency string for the transaction
-    pub currency: String,
+    /// The currency of the transaction
+    pub currency: Currency,
 
     /// The transaction description
     pub description: String,
@@ -81,10 +102,82 @@ pub struct Transaction {
     /// This is `None` if the transaction is authorised, but not yet complete.
     #[serde(deserialize_with = "empty_string_as_none")]
     pub settled: Option<DateTime<Utc>>,
+
+    /// The timestamp when the transaction was last updated
+    #[serde(default)]
+    pub updated: Option<DateTime<Utc>>,
+
+    /// The amount in the currency the transaction was made in, in its minor
+    /// units
+    ///
+    /// This differs from `amount` for spending in a foreign currency.
+    #[serde(default)]
+    pub local_amount: Option<i64>,
+
+    /// The currency the transaction was made in
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    pub local_currency: Option<Currency>,
+
+    /// The other side of a bank transfer or payment between Monzo users
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub counterparty: Option<Counterparty>,
+
+    /// The payment scheme the transaction was made through
+    #[serde(default)]
+    pub scheme: Option<Scheme>,
+
+    /// Fees charged for the transaction
+    ///
+    /// Monzo doesn't document the shape of this object, so it's left as raw
+    /// JSON.
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub fees: serde_json::Map<String, serde_json::Value>,
+
+    /// Labels attached to the transaction
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub labels: Vec<String>,
+
+    /// An id used by the payment scheme to deduplicate the transaction
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    pub dedupe_id: Option<String>,
+
+    /// The ID of the user who made the transaction
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    pub user_id: Option<String>,
+
+    /// Whether the account holder initiated the transaction
+    #[serde(default)]
+    pub originator: bool,
+
+    /// Details of an international payment
+    ///
+    /// Monzo doesn't document the shape of this object, so it's left as raw
+    /// JSON.
+    #[serde(default)]
+    pub international: Option<serde_json::Map<String, serde_json::Value>>,
+
+    /// Any fields which aren't modelled by this struct
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: crate::Extra,
+}
+
+impl Transaction {
+    /// The amount of the transaction
+    #[must_use]
+    pub fn amount_money(&self) -> Money {
+        Money::new(self.amount, self.currency.clone())
+    }
+
+    /// The amount of the transaction in the currency it was made in
+    #[must_use]
+    pub fn local_amount_money(&self) -> Option<Money> {
+        Some(Money::new(self.local_amount?, self.local_currency.clone()?))
+    }
 }
 
 /// The set of reasons for which a monzo transaction may be declined
-#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 #[non_exhaustive]
 pub enum DeclineReason {
@@ -126,7 +219,7 @@ pub enum DeclineReason {
 ///
 /// An id or a struct may be returned depending on whether the 'expand merchant'
 /// flag is set in the transactions request.
-#[derive(Deserialize, Debug, PartialEq, Clone)]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
 #[serde(untagged)]
 pub enum MerchantInfo {
     /// A unique ID associated with a merchant
@@ -137,7 +230,8 @@ pub enum MerchantInfo {
 }
 
 /// Merchant details
-#[derive(Deserialize, Debug, PartialEq, Clone)]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
+#[non_exhaustive]
 #[allow(missing_docs)]
 pub struct Merchant {
     pub address: Address,
@@ -147,11 +241,60 @@ pub struct Merchant {
     pub logo: String,
     pub emoji: String,
     pub name: String,
-    pub category: String,
+    pub category: Category,
+    /// Any fields which aren't modelled by this struct
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: crate::Extra,
+}
+
+/// The other side of a bank transfer or payment between Monzo users
+#[derive(Deserialize, Serialize, Debug, Default, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct Counterparty {
+    /// The counterparty's account number
+    #[serde(default)]
+    pub account_number: Option<String>,
+
+    /// The counterparty's sort code
+    #[serde(default)]
+    pub sort_code: Option<String>,
+
+    /// The counterparty's name
+    #[serde(default)]
+    pub name: Option<String>,
+
+    /// The counterparty's Monzo user ID, if they have one
+    #[serde(default)]
+    pub user_id: Option<String>,
+
+    /// Any fields which aren't modelled by this struct
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: crate::Extra,
+}
+
+string_enum! {
+    /// The payment scheme a transaction was made through
+    pub enum Scheme {
+        /// Any other scheme
+        Other(String),
+        /// A card payment
+        Mastercard = "mastercard",
+        /// A UK bank transfer
+        FasterPayments = "payport_faster_payments",
+        /// A direct debit or other BACS payment
+        Bacs = "bacs",
+        /// A payment between Monzo users
+        P2pPayment = "p2p_payment",
+        /// A transfer to or from a pot
+        PotTransfer = "uk_retail_pot",
+    }
 }
 
 /// Address details
-#[derive(Deserialize, Debug, PartialEq, Clone)]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
+#[non_exhaustive]
 #[allow(missing_docs)]
 pub struct Address {
     pub address: String,
@@ -161,6 +304,10 @@ pub struct Address {
     pub longitude: f32,
     pub postcode: String,
     pub region: String,
+    /// Any fields which aren't modelled by this struct
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
 #[derive(Serialize, Default, Debug)]
@@ -190,7 +337,8 @@ pub enum Since {
 #[cfg(test)]
 mod tests {
     #![allow(clippy::too_many_lines, clippy::non_ascii_literal)]
-    use super::Transaction;
+    use super::{Category, Counterparty, Scheme, Transaction};
+    use crate::money::{Currency, Money};
 
     #[test]
     fn deserialise_expanded_transaction() {
@@ -274,7 +422,25 @@ mod tests {
         }
         "##;
 
-        serde_json::from_str::<Transaction>(raw).expect("couldn't decode Transaction from json");
+        let transaction = serde_json::from_str::<Transaction>(raw)
+            .expect("couldn't decode Transaction from json");
+        assert_eq!(transaction.category, Category::Bills);
+        assert_eq!(transaction.categories[&Category::Bills], -5000);
+        assert_eq!(transaction.scheme, Some(Scheme::Mastercard));
+        assert_eq!(
+            transaction.local_amount_money(),
+            Some(Money::new(-3900, Currency::GBP))
+        );
+        assert_eq!(transaction.counterparty, None);
+
+        #[cfg(feature = "extra-fields")]
+        {
+            assert!(transaction.extra.contains_key("atm_fees_detailed"));
+            let Some(super::MerchantInfo::Details(merchant)) = &transaction.merchant else {
+                panic!("expected merchant details");
+            };
+            assert_eq!(merchant.extra["online"], true);
+        }
     }
 
     #[test]
@@ -436,7 +602,24 @@ mod tests {
         }
         "#;
 
-        serde_json::from_str::<Transaction>(raw).expect("couldn't decode Transaction from json");
+        let transaction = serde_json::from_str::<Transaction>(raw)
+            .expect("couldn't decode Transaction from json");
+        assert_eq!(
+            transaction.counterparty,
+            Some(Counterparty {
+                account_number: Some("12345678".to_string()),
+                sort_code: Some("987654".to_string()),
+                name: Some("John Smith".to_string()),
+                user_id: Some("anonuser_1234567a89b123456cd7e8".to_string()),
+                // the extra fields, when they're enabled
+                ..Counterparty::default()
+            })
+        );
+        assert_eq!(
+            transaction.scheme,
+            Some(Scheme::Other("payport_faster_pajments".to_string()))
+        );
+        assert_eq!(transaction.user_id, None);
     }
 
     #[test]
@@ -580,4 +763,51 @@ mod tests {
 
         serde_json::from_str::<Response>(raw).expect("couldn't decode Transaction from json");
     }
+
+    #[test]
+    fn deserialise_foreign_currency_transaction() {
+        let raw = r#"
+        {
+          "id": "tx_0000A1aBC2Dbc34Ede5fEG",
+          "created": "2021-07-02T09:00:00.000Z",
+          "description": "CAFE DE PARIS",
+          "amount": -870,
+          "fees": {},
+          "currency": "GBP",
+          "merchant": null,
+          "notes": "",
+          "metadata": {},
+          "labels": null,
+          "attachments": null,
+          "international": null,
+          "category": "eating_out",
+          "categories": null,
+          "is_load": false,
+          "settled": "",
+          "local_amount": -1000,
+          "local_currency": "EUR",
+          "account_id": "acc_99999aAbBc0DEFH1I2JdKL",
+          "counterparty": {},
+          "include_in_spending": true,
+          "can_be_excluded_from_breakdown": false,
+          "can_be_made_subscription": false,
+          "can_split_the_bill": false,
+          "can_add_to_tab": false,
+          "amount_is_pending": true
+        }
+        "#;
+
+        let transaction = serde_json::from_str::<Transaction>(raw)
+            .expect("couldn't decode Transaction from json");
+        assert_eq!(transaction.amount_money(), Money::new(-870, Currency::GBP));
+        assert_eq!(
+            transaction.local_amount_money(),
+            Some(Money::new(-1000, Currency::EUR))
+        );
+
+        let raw = raw.replace(r#""local_currency": "EUR""#, r#""local_currency": """#);
+        let transaction = serde_json::from_str::<Transaction>(&raw).unwrap();
+        assert_eq!(transaction.local_currency, None);
+        assert_eq!(transaction.local_amount_money(), None);
+    }
 }
@@ -0,0 +1,70 @@
+use serde::Deserialize;
+
+use super::Transaction;
+use crate::{client, endpoints::Endpoint, Result};
+
+/// A request to update the metadata of a transaction
+///
+/// Use the builder-style methods to add or remove metadata keys
+#[derive(Debug)]
+#[must_use]
+pub struct Request<'a, C>
+where
+    C: client::Inner,
+{
+    client: &'a C,
+    endpoint: String,
+    form: Vec<(String, &'a str)>,
+}
+
+impl<C> Endpoint for Request<'_, C>
+where
+    C: client::Inner,
+{
+    const METHOD: reqwest::Method = reqwest::Method::PATCH;
+
+    fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    fn form(&self) -> Option<&dyn erased_serde::Serialize> {
+        Some(&self.form)
+    }
+}
+
+impl<'a, C> Request<'a, C>
+where
+    C: client::Inner,
+{
+    pub(crate) fn new(client: &'a C, transaction_id: &str) -> Self {
+        let endpoint = format!("/transactions/{transaction_id}");
+        Self {
+            client,
+            endpoint,
+            form: Vec::new(),
+        }
+    }
+
+    /// Set a metadata key on the transaction
+    pub fn metadata(mut self, key: &str, value: &'a str) -> Self {
+        self.form.push((format!("metadata[{key}]"), value));
+        self
+    }
+
+    /// Remove a metadata key from the transaction
+    pub fn remove_metadata(self, key: &str) -> Self {
+        self.metadata(key, "")
+    }
+
+    /// Consume the request and return the updated [`Transaction`]
+    pub async fn send(self) -> Result<Transaction> {
+        #[derive(Deserialize)]
+        struct Response {
+            transaction: Transaction,
+        }
+
+        let response: Response = self.client.handle_request(&self).await?;
+
+        Ok(response.transaction)
+    }
+}
@@ -0,0 +1,84 @@
+use crate::endpoints::utils::string_enum;
+
+string_enum! {
+    /// The category of a transaction or merchant
+    ///
+    /// Categories which aren't listed here, including categories created by the
+    /// user, are represented by [`Category::Custom`]. Categories compare by
+    /// their id, so `Category::Custom("general".to_string())` is equal to
+    /// [`Category::General`].
+    pub enum Category {
+        /// Any other category, by its id
+        Custom(String),
+        /// `general`
+        General = "general",
+        /// `eating_out`
+        EatingOut = "eating_out",
+        /// `expenses`
+        Expenses = "expenses",
+        /// `transport`
+        Transport = "transport",
+        /// `cash`
+        Cash = "cash",
+        /// `bills`
+        Bills = "bills",
+        /// `entertainment`
+        Entertainment = "entertainment",
+        /// `shopping`
+        Shopping = "shopping",
+        /// `holidays`
+        Holidays = "holidays",
+        /// `groceries`
+        Groceries = "groceries",
+        /// `personal_care`
+        PersonalCare = "personal_care",
+        /// `family`
+        Family = "family",
+        /// `charity`
+        Charity = "charity",
+        /// `finances`
+        Finances = "finances",
+        /// `gifts`
+        Gifts = "gifts",
+        /// `income`
+        Income = "income",
+        /// `savings`
+        Savings = "savings",
+        /// `transfers`
+        Transfers = "transfers",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::Category;
+
+    #[test_case(r#""eating_out""# => Category::EatingOut; "known")]
+    #[test_case(r#""category_0000""# => Category::Custom("category_0000".to_string()); "custom")]
+    fn deserialise(raw: &str) -> Category {
+        serde_json::from_str(raw).unwrap()
+    }
+
+    #[test_case(&Category::Groceries => r#""groceries""#; "known")]
+    #[test_case(&Category::Custom("category_0000".to_string()) => r#""category_0000""#; "custom")]
+    fn serialise(category: &Category) -> String {
+        serde_json::to_string(category).unwrap()
+    }
+
+    #[test]
+    fn custom_known_category() {
+        use std::collections::HashMap;
+
+        let custom = Category::Custom("general".to_string());
+        assert_eq!(custom, Category::General);
+        assert_ne!(
+            Category::Custom("category_0000".to_string()),
+            Category::General
+        );
+
+        let categories = HashMap::from([(Category::General, -500)]);
+        assert_eq!(categories.get(&custom), Some(&-500));
+    }
+}
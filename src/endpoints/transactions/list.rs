@@ -1,4 +1,5 @@
 use chrono::{DateTime, Utc};
+use futures_util::{stream, Stream, TryStreamExt};
 use serde::{Deserialize, Serialize};
 
 use super::{Pagination, Since, Transaction};
@@ -66,7 +67,10 @@ where
         self
     }
 
-    /// Set the maximum number of transactions to be returned
+    /// Set the maximum number of transactions to be returned.
+    ///
+    /// When [streaming](Self::stream), this sets the number of transactions
+    /// requested per page.
     pub const fn limit(mut self, limit: u16) -> Self {
         self.query.pagination.limit = Some(limit);
         self
@@ -90,8 +94,62 @@ where
 
         Ok(response.transactions)
     }
+
+    /// Consume the request and return a [`Stream`] of [`Transaction`]s,
+    /// requesting further pages as required.
+    ///
+    /// Each page is requested using the id of the last transaction of the
+    /// previous page as a cursor, until there are no more transactions (or the
+    /// [`before`](Self::before) bound is reached). The page size is set using
+    /// [`limit`](Self::limit), and defaults to 100.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use futures_util::TryStreamExt;
+    /// use monzo::Client;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = Client::new("ACCESS_TOKEN");
+    ///
+    /// let transactions: Vec<_> = client
+    ///     .transactions("ACCOUNT_ID")
+    ///     .limit(50)
+    ///     .stream()
+    ///     .try_collect()
+    ///     .await?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stream(mut self) -> impl Stream<Item = Result<Transaction>> + 'a {
+        let page_size = *self.query.pagination.limit.get_or_insert(DEFAULT_PAGE_SIZE);
+        let before = self.query.pagination.before;
+
+        stream::try_unfold(Some(self), move |request| async move {
+            let Some(mut request) = request else {
+                return Result::Ok(None);
+            };
+
+            let response: Response = request.client.handle_request(&request).await?;
+            let page = response.transactions;
+
+            let next = page
+                .last()
+                .filter(|_| page.len() >= usize::from(page_size))
+                .filter(|last| before.is_none_or(|before| last.created < before))
+                .map(|last| {
+                    request.query.pagination.since = Some(Since::ObjectId(last.id.clone()));
+                    request
+                });
+
+            Ok(Some((stream::iter(page.into_iter().map(Ok)), next)))
+        })
+        .try_flatten()
+    }
 }
 
+const DEFAULT_PAGE_SIZE: u16 = 100;
+
 #[derive(Serialize, Debug)]
 struct Query<'a> {
     account_id: &'a str,
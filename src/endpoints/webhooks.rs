@@ -0,0 +1,57 @@
+//! endpoints for registering webhooks, and the events Monzo sends to them
+
+use serde::{Deserialize, Serialize};
+
+mod list;
+pub use list::Request as List;
+mod register;
+pub use register::Request as Register;
+mod delete;
+pub use delete::Request as Delete;
+mod event;
+pub use event::{parse_event, verify_secret, Event, ParseError};
+#[cfg(feature = "webhook-server")]
+mod server;
+#[cfg(feature = "webhook-server")]
+pub use server::Server;
+
+/// A webhook registered against a Monzo account
+///
+/// Monzo will send a POST request to the webhook's URL each time an event
+/// occurs on the account.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Webhook {
+    /// Unique ID for this webhook
+    pub id: String,
+
+    /// The ID of the account the webhook is registered against
+    pub account_id: String,
+
+    /// The URL which events are sent to
+    pub url: String,
+
+    /// Any fields which aren't modelled by this struct
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: crate::Extra,
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::Webhook;
+
+    #[test]
+    fn deserialise() {
+        let raw = r#"
+        {
+            "account_id": "acc_000091yf79yMwNaZHhHGzp",
+            "id": "webhook_000091yhhOmrXQaVZ1Irsv",
+            "url": "http://example.com/callback"
+        }
+        "#;
+
+        serde_json::from_str::<Webhook>(raw).expect("couldn't decode Webhook from json");
+    }
+}
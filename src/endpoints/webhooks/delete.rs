@@ -0,0 +1,22 @@
+use crate::endpoints::Endpoint;
+
+/// An object representing a request to the Monzo API to delete a webhook
+#[derive(Debug)]
+pub struct Request {
+    endpoint: String,
+}
+
+impl Request {
+    pub(crate) fn new(webhook_id: &str) -> Self {
+        let endpoint = format!("/webhooks/{webhook_id}");
+        Self { endpoint }
+    }
+}
+
+impl Endpoint for Request {
+    const METHOD: reqwest::Method = reqwest::Method::DELETE;
+
+    fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+}
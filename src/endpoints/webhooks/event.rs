@@ -0,0 +1,342 @@
+use std::fmt;
+
+use serde::{
+    de::{self, IgnoredAny, MapAccess, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::endpoints::transactions::Transaction;
+
+/// An event sent by Monzo to a registered [`Webhook`](super::Webhook)
+///
+/// Use [`parse_event`] to decode the body of a webhook request.
+///
+/// Serializing an event produces the JSON body Monzo sends, so it can be parsed
+/// again with [`parse_event`]. An [`Event::Unknown`] is written without its
+/// `data`.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Event {
+    /// A new transaction was created on the account
+    TransactionCreated(Box<Transaction>),
+
+    /// An existing transaction was updated, for example when it settles
+    TransactionUpdated(Box<Transaction>),
+
+    /// An event type which this crate doesn't recognise, with the event's
+    /// `type` field
+    Unknown(String),
+}
+
+impl Event {
+    fn transaction_variant(event_type: &str) -> Option<fn(Box<Transaction>) -> Self> {
+        match event_type {
+            "transaction.created" => Some(Self::TransactionCreated),
+            "transaction.updated" => Some(Self::TransactionUpdated),
+            _ => None,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Event {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(field_identifier, rename_all = "lowercase")]
+        enum Field {
+            Type,
+            Data,
+            #[serde(other)]
+            Other,
+        }
+
+        struct EventVisitor;
+
+        impl<'de> Visitor<'de> for EventVisitor {
+            type Value = Event;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a webhook event")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Event, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut event_type: Option<String> = None;
+                let mut transaction: Option<Box<Transaction>> = None;
+                // `data` which arrived before `type`, so couldn't be decoded yet
+                let mut buffered: Option<serde_json::Value> = None;
+                let mut seen_data = false;
+
+                while let Some(field) = map.next_key()? {
+                    match field {
+                        Field::Type if event_type.is_some() => {
+                            return Err(de::Error::duplicate_field("type"));
+                        }
+                        Field::Type => event_type = Some(map.next_value()?),
+                        Field::Data if seen_data => {
+                            return Err(de::Error::duplicate_field("data"));
+                        }
+                        Field::Data => {
+                            seen_data = true;
+                            match event_type.as_deref() {
+                                Some(t) if Event::transaction_variant(t).is_some() => {
+                                    transaction = Some(map.next_value()?);
+                                }
+                                Some(_) => {
+                                    map.next_value::<IgnoredAny>()?;
+                                }
+                                None => buffered = Some(map.next_value()?),
+                            }
+                        }
+                        Field::Other => {
+                            map.next_value::<IgnoredAny>()?;
+                        }
+                    }
+                }
+
+                let event_type = event_type.ok_or_else(|| de::Error::missing_field("type"))?;
+                let Some(variant) = Event::transaction_variant(&event_type) else {
+                    return Ok(Event::Unknown(event_type));
+                };
+                let transaction = match (transaction, buffered) {
+                    (Some(transaction), _) => transaction,
+                    (None, Some(data)) => {
+                        serde_json::from_value(data).map_err(de::Error::custom)?
+                    }
+                    (None, None) => return Err(de::Error::missing_field("data")),
+                };
+
+                Ok(variant(transaction))
+            }
+        }
+
+        deserializer.deserialize_map(EventVisitor)
+    }
+}
+
+impl Serialize for Event {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        #[serde(tag = "type", content = "data")]
+        enum Known<'a> {
+            #[serde(rename = "transaction.created")]
+            TransactionCreated(&'a Transaction),
+
+            #[serde(rename = "transaction.updated")]
+            TransactionUpdated(&'a Transaction),
+        }
+
+        #[derive(Serialize)]
+        struct Unknown<'a> {
+            #[serde(rename = "type")]
+            event_type: &'a str,
+        }
+
+        match self {
+            Self::TransactionCreated(transaction) => {
+                Known::TransactionCreated(transaction).serialize(serializer)
+            }
+            Self::TransactionUpdated(transaction) => {
+                Known::TransactionUpdated(transaction).serialize(serializer)
+            }
+            Self::Unknown(event_type) => Unknown { event_type }.serialize(serializer),
+        }
+    }
+}
+
+/// An error decoding a webhook [`Event`]
+#[derive(Debug, thiserror::Error)]
+#[error("failed to decode webhook event at '{path}': {source}")]
+pub struct ParseError {
+    path: String,
+    source: serde_json::Error,
+}
+
+impl ParseError {
+    /// The path to the field which failed to decode
+    #[must_use]
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+/// Parse the body of a webhook request into an [`Event`]
+///
+/// This does no I/O, so it can be used with any HTTP server.
+///
+/// # Example
+/// ```
+/// use monzo::webhooks::{parse_event, Event};
+///
+/// # let body: &[u8] = br#"{"type": "unrecognised", "data": {}}"#;
+/// match parse_event(body)? {
+///     Event::TransactionCreated(transaction) => println!("{}", transaction.description),
+///     _ => (),
+/// }
+/// # Ok::<(), monzo::webhooks::ParseError>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the body isn't a valid webhook event.
+pub fn parse_event(body: &[u8]) -> Result<Event, ParseError> {
+    decode(body)
+}
+
+/// Check a secret token taken from a webhook request against the expected
+/// secret
+///
+/// Monzo doesn't sign webhook payloads, so the only way to tell that a request
+/// really came from Monzo is to register the webhook with a URL containing a
+/// secret, such as `https://example.com/webhook/<secret>`, and check that
+/// incoming requests carry it. The comparison takes the same time however much
+/// of the token matches, so the secret can't be discovered by timing requests.
+///
+/// # Example
+/// ```
+/// use monzo::webhooks::verify_secret;
+///
+/// # let path = "/webhook/SECRET";
+/// let token = path.trim_start_matches("/webhook/");
+/// assert!(verify_secret(token, "SECRET"));
+/// ```
+#[must_use]
+pub fn verify_secret(token: &str, secret: &str) -> bool {
+    let (token, secret) = (token.as_bytes(), secret.as_bytes());
+    token.len() == secret.len()
+        && token
+            .iter()
+            .zip(secret)
+            .fold(0, |acc, (x, y)| acc | (x ^ y))
+            == 0
+}
+
+fn decode<'de, T>(body: &'de [u8]) -> Result<T, ParseError>
+where
+    T: Deserialize<'de>,
+{
+    let deserializer = &mut serde_json::Deserializer::from_slice(body);
+    serde_path_to_error::deserialize(deserializer).map_err(|e| ParseError {
+        path: e.path().to_string(),
+        source: e.into_inner(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::non_ascii_literal)]
+    use super::{parse_event, verify_secret, Event};
+
+    #[test]
+    fn transaction_created() {
+        let raw = r#"
+        {
+            "type": "transaction.created",
+            "data": {
+                "account_id": "acc_00008gju41AHyfLUzBUk8A",
+                "amount": -350,
+                "amount_is_pending": true,
+                "can_add_to_tab": false,
+                "can_be_excluded_from_breakdown": true,
+                "can_be_made_subscription": false,
+                "can_split_the_bill": true,
+                "category": "eating_out",
+                "created": "2015-09-04T14:28:40Z",
+                "currency": "GBP",
+                "description": "Ozone Coffee Roasters",
+                "id": "tx_00008zjky19HyFLAzlUk7t",
+                "include_in_spending": true,
+                "is_load": false,
+                "merchant": {
+                    "address": {
+                        "address": "98 Southgate Road",
+                        "city": "London",
+                        "country": "GB",
+                        "latitude": 51.54151,
+                        "longitude": -0.08482400000002599,
+                        "postcode": "N1 3JD",
+                        "region": "Greater London"
+                    },
+                    "category": "eating_out",
+                    "created": "2015-08-22T12:20:18Z",
+                    "group_id": "grp_00008zIcpbBOaAr7TTP3sv",
+                    "id": "merch_00008zIcpbAKe8shBxXUtl",
+                    "logo": "https://pbs.twimg.com/profile_images/527043602623389696/68_SgUWJ.jpeg",
+                    "emoji": "🍞",
+                    "name": "The De Beauvoir Deli Co.",
+                    "online": false
+                },
+                "metadata": {},
+                "notes": "Salmon sandwich 🍞",
+                "settled": ""
+            }
+        }
+        "#;
+
+        let event = parse_event(raw.as_bytes()).expect("couldn't decode Event from json");
+        let Event::TransactionCreated(transaction) = &event else {
+            panic!("expected a transaction.created event, got {event:?}");
+        };
+        assert_eq!(transaction.id, "tx_00008zjky19HyFLAzlUk7t");
+
+        let body = serde_json::to_vec(&event).unwrap();
+        assert_eq!(parse_event(&body).unwrap(), event);
+
+        // `Value` sorts its keys, so this decodes `data` before `type`
+        let value = serde_json::to_value(&event).unwrap();
+        assert_eq!(serde_json::from_value::<Event>(value).unwrap(), event);
+    }
+
+    #[test]
+    fn unknown_event_type() {
+        let raw = r#"
+        {
+            "type": "account.balance_changed",
+            "data": {
+                "account_id": "acc_00008gju41AHyfLUzBUk8A"
+            }
+        }
+        "#;
+
+        let event = parse_event(raw.as_bytes()).expect("couldn't decode Event from json");
+        assert_eq!(event, Event::Unknown("account.balance_changed".to_string()));
+
+        let deserialized: Event =
+            serde_json::from_str(raw).expect("couldn't decode Event from json");
+        assert_eq!(deserialized, event);
+
+        let body = serde_json::to_vec(&event).unwrap();
+        assert_eq!(parse_event(&body).unwrap(), event);
+    }
+
+    #[test]
+    fn invalid_payload() {
+        let raw = r#"
+        {
+            "type": "transaction.created",
+            "data": {
+                "id": "tx_00008zjky19HyFLAzlUk7t",
+                "amount": "not a number"
+            }
+        }
+        "#;
+
+        let error = parse_event(raw.as_bytes()).unwrap_err();
+        assert_eq!(error.path(), "data.amount");
+    }
+
+    #[test]
+    fn secret() {
+        assert!(verify_secret("SECRET", "SECRET"));
+        assert!(!verify_secret("SECRes", "SECRET"));
+        assert!(!verify_secret("SECRET_", "SECRET"));
+        assert!(!verify_secret("", "SECRET"));
+    }
+}
@@ -0,0 +1,33 @@
+use serde::Serialize;
+
+use crate::endpoints::Endpoint;
+
+/// An object representing a request to the Monzo API for a list of webhooks
+#[derive(Debug)]
+pub struct Request<'a> {
+    query: Query<'a>,
+}
+
+impl<'a> Request<'a> {
+    pub(crate) const fn new(account_id: &'a str) -> Self {
+        let query = Query { account_id };
+        Self { query }
+    }
+}
+
+impl Endpoint for Request<'_> {
+    const METHOD: reqwest::Method = reqwest::Method::GET;
+
+    fn endpoint(&self) -> &'static str {
+        "/webhooks"
+    }
+
+    fn query(&self) -> Option<&dyn erased_serde::Serialize> {
+        Some(&self.query)
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Query<'a> {
+    account_id: &'a str,
+}
@@ -0,0 +1,34 @@
+use serde::Serialize;
+
+use crate::endpoints::Endpoint;
+
+/// An object representing a request to the Monzo API to register a webhook
+#[derive(Debug)]
+pub struct Request<'a> {
+    form: Form<'a>,
+}
+
+impl<'a> Request<'a> {
+    pub(crate) const fn new(account_id: &'a str, url: &'a str) -> Self {
+        let form = Form { account_id, url };
+        Self { form }
+    }
+}
+
+impl Endpoint for Request<'_> {
+    const METHOD: reqwest::Method = reqwest::Method::POST;
+
+    fn endpoint(&self) -> &'static str {
+        "/webhooks"
+    }
+
+    fn form(&self) -> Option<&dyn erased_serde::Serialize> {
+        Some(&self.form)
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Form<'a> {
+    account_id: &'a str,
+    url: &'a str,
+}
@@ -0,0 +1,289 @@
+use std::{convert::Infallible, future::Future, io, net::SocketAddr, sync::Arc, time::Duration};
+
+use http_body_util::{BodyExt, Empty, Limited};
+use hyper::{
+    body::{Bytes, Incoming},
+    server::conn::http1,
+    service::service_fn,
+    Method, Request, Response, StatusCode,
+};
+use hyper_util::rt::{TokioIo, TokioTimer};
+use tokio::{
+    net::{TcpListener, ToSocketAddrs},
+    sync::mpsc,
+    task::{JoinError, JoinSet},
+};
+
+use super::{parse_event, verify_secret, Event};
+
+/// The largest request body the server will accept
+const MAX_BODY_SIZE: usize = 1024 * 1024;
+
+/// The default time a client has to send each part of a request
+const DEFAULT_READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The default number of connections the server handles at once
+const DEFAULT_MAX_CONNECTIONS: usize = 256;
+
+/// The longest the server waits before accepting connections again, after
+/// failing to accept one
+const MAX_ACCEPT_BACKOFF: Duration = Duration::from_secs(1);
+
+/// A minimal HTTP server for receiving webhook [`Event`]s
+///
+/// The server accepts POST requests, parses the body into an [`Event`] and
+/// passes it to a handler. Monzo doesn't sign webhook payloads, so the server
+/// can be given a secret token which must appear as the request path. Register
+/// the webhook with a URL like `https://example.com/<secret>`.
+///
+/// The server responds with
+/// - `200 OK` once the handler has accepted the event
+/// - `400 Bad Request` if the body isn't a valid event
+/// - `404 Not Found` if the path doesn't match the secret
+/// - `405 Method Not Allowed` for anything other than a POST
+/// - `408 Request Timeout` if the body isn't received within the [read
+///   timeout](Self::read_timeout)
+///
+/// Each connection serves a single request, and is closed if the client is
+/// too slow to send its headers. Once
+/// [`max_connections`](Self::max_connections) requests are in progress, new
+/// connections wait until one finishes.
+///
+/// *This requires the `webhook-server` feature.*
+///
+/// # Example
+/// ```no_run
+/// use monzo::webhooks::{Event, Server};
+/// use tokio::sync::mpsc;
+///
+/// # #[tokio::main]
+/// # async fn main() -> std::io::Result<()> {
+/// let (sender, mut receiver) = mpsc::channel(16);
+///
+/// let server = Server::bind("0.0.0.0:8080").await?.secret("SECRET");
+/// tokio::spawn(server.serve_channel(sender));
+///
+/// while let Some(event) = receiver.recv().await {
+///     if let Event::TransactionCreated(transaction) = event {
+///         println!("{}", transaction.description);
+///     }
+/// }
+/// #
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Server {
+    listener: TcpListener,
+    path: String,
+    read_timeout: Duration,
+    max_connections: usize,
+}
+
+impl Server {
+    /// Create a server listening on the given address
+    pub async fn bind(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr).await?;
+        Ok(Self::from_listener(listener))
+    }
+
+    /// Create a server from an existing [`TcpListener`]
+    #[must_use]
+    pub fn from_listener(listener: TcpListener) -> Self {
+        Self {
+            listener,
+            path: "/".to_string(),
+            read_timeout: DEFAULT_READ_TIMEOUT,
+            max_connections: DEFAULT_MAX_CONNECTIONS,
+        }
+    }
+
+    /// Only accept requests whose path is `/<secret>`
+    ///
+    /// By default requests are accepted on `/`.
+    #[must_use]
+    pub fn secret(mut self, secret: &str) -> Self {
+        self.path = format!("/{secret}");
+        self
+    }
+
+    /// Set how long a client has to send the request headers, and then the
+    /// request body
+    ///
+    /// Defaults to 30 seconds.
+    #[must_use]
+    pub const fn read_timeout(mut self, read_timeout: Duration) -> Self {
+        self.read_timeout = read_timeout;
+        self
+    }
+
+    /// Set how many connections the server handles at once
+    ///
+    /// Defaults to 256. A limit of 0 is treated as 1.
+    #[must_use]
+    pub fn max_connections(mut self, max_connections: usize) -> Self {
+        self.max_connections = max_connections.max(1);
+        self
+    }
+
+    /// The address the server is listening on
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Run the server, calling `handler` with each event
+    ///
+    /// The response is sent once the handler's future completes. If the
+    /// handler is slow, Monzo may time out and resend the event.
+    ///
+    /// The server runs until the future is dropped, which also closes any
+    /// connections still in progress.
+    ///
+    /// # Errors
+    ///
+    /// This never returns an error at the moment. If accepting a connection
+    /// fails (for example because the process has run out of file
+    /// descriptors), the error is logged and the server backs off before
+    /// trying again.
+    pub async fn serve<F, Fut>(self, handler: F) -> io::Result<Infallible>
+    where
+        F: Fn(Event) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send,
+    {
+        let state = Arc::new(State {
+            path: self.path,
+            read_timeout: self.read_timeout,
+            handler,
+        });
+
+        let mut http = http1::Builder::new();
+        http.timer(TokioTimer::new())
+            .header_read_timeout(self.read_timeout)
+            .keep_alive(false);
+
+        let mut connections = JoinSet::new();
+        let mut backoff = Duration::ZERO;
+
+        loop {
+            while let Some(result) = connections.try_join_next() {
+                connection_finished(result);
+            }
+            if connections.len() >= self.max_connections {
+                if let Some(result) = connections.join_next().await {
+                    connection_finished(result);
+                }
+            }
+
+            let stream = match self.listener.accept().await {
+                Ok((stream, _)) => {
+                    backoff = Duration::ZERO;
+                    stream
+                }
+                Err(e) => {
+                    backoff = (backoff * 2)
+                        .max(Duration::from_millis(10))
+                        .min(MAX_ACCEPT_BACKOFF);
+                    tracing::warn!(
+                        "failed to accept webhook connection, retrying in {backoff:?}: {e}"
+                    );
+                    tokio::time::sleep(backoff).await;
+                    continue;
+                }
+            };
+            let state = Arc::clone(&state);
+            let http = http.clone();
+
+            connections.spawn(async move {
+                let service = service_fn(|request| {
+                    let state = Arc::clone(&state);
+                    async move { Ok::<_, Infallible>(state.handle(request).await) }
+                });
+
+                if let Err(e) = http.serve_connection(TokioIo::new(stream), service).await {
+                    tracing::debug!("webhook connection failed: {e}");
+                }
+            });
+        }
+    }
+
+    /// Run the server, sending each event to a channel
+    ///
+    /// If the channel is full, the response is delayed until there is
+    /// capacity.
+    ///
+    /// Like [`serve`](Self::serve), this runs until the future is dropped.
+    ///
+    /// # Errors
+    ///
+    /// This never returns an error at the moment, see [`serve`](Self::serve).
+    pub async fn serve_channel(self, sender: mpsc::Sender<Event>) -> io::Result<Infallible> {
+        self.serve(move |event| {
+            let sender = sender.clone();
+            async move {
+                if sender.send(event).await.is_err() {
+                    tracing::warn!("webhook event dropped, the receiver has been closed");
+                }
+            }
+        })
+        .await
+    }
+}
+
+struct State<F> {
+    path: String,
+    read_timeout: Duration,
+    handler: F,
+}
+
+impl<F, Fut> State<F>
+where
+    F: Fn(Event) -> Fut + Sync,
+    Fut: Future<Output = ()> + Send,
+{
+    async fn handle(&self, request: Request<Incoming>) -> Response<Empty<Bytes>> {
+        if !verify_secret(request.uri().path(), &self.path) {
+            return status(StatusCode::NOT_FOUND);
+        }
+
+        if request.method() != Method::POST {
+            return status(StatusCode::METHOD_NOT_ALLOWED);
+        }
+
+        let body = Limited::new(request.into_body(), MAX_BODY_SIZE).collect();
+        let body = match tokio::time::timeout(self.read_timeout, body).await {
+            Ok(Ok(body)) => body.to_bytes(),
+            Ok(Err(e)) => {
+                tracing::warn!("failed to read webhook body: {e}");
+                return status(StatusCode::BAD_REQUEST);
+            }
+            Err(_) => {
+                tracing::warn!("timed out reading webhook body");
+                return status(StatusCode::REQUEST_TIMEOUT);
+            }
+        };
+
+        let event = match parse_event(&body) {
+            Ok(event) => event,
+            Err(e) => {
+                tracing::warn!("{e}");
+                return status(StatusCode::BAD_REQUEST);
+            }
+        };
+
+        (self.handler)(event).await;
+
+        status(StatusCode::OK)
+    }
+}
+
+fn connection_finished(result: Result<(), JoinError>) {
+    if let Err(e) = result {
+        tracing::error!("webhook connection task failed: {e}");
+    }
+}
+
+fn status(status: StatusCode) -> Response<Empty<Bytes>> {
+    let mut response = Response::new(Empty::new());
+    *response.status_mut() = status;
+    response
+}
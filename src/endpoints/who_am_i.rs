@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use super::Endpoint;
 
@@ -14,7 +14,8 @@ impl Endpoint for Request {
 
 /// The response returned by the [`Client::who_am_i`](crate::Client::who_am_i)
 /// method.
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub struct Response {
     /// Whether the current user is authenticated
     pub authenticated: bool,
@@ -24,4 +25,9 @@ pub struct Response {
 
     /// The unique identifier of the current user
     pub user_id: String,
+
+    /// Any fields which aren't modelled by this struct
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
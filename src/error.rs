@@ -1,20 +1,80 @@
+use std::{fmt, time::Duration};
+
 use reqwest::StatusCode;
+use serde::Deserialize;
 
 /// Common error type for anything that can go wrong with this crate
 #[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
 pub enum Error {
     /// HTTP Errors
     #[error("HTTP error: {0}")]
     Http(#[from] reqwest::Error),
 
+    /// I/O Errors
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
     /// Serialisation/Deserialisation Errors
     #[error("unable to serialise/deserialise struct")]
     Serde(#[from] serde_json::Error),
 
+    /// Error returned when a successful API response can't be deserialised
+    #[error("unable to decode response at '{path}': {source}")]
+    Decode {
+        /// The path to the field which couldn't be decoded, such as
+        /// `transactions[3].merchant`
+        path: String,
+
+        /// The raw response body
+        body: String,
+
+        /// The underlying deserialisation error
+        source: serde_json::Error,
+    },
+
     /// Error returned when the access token has expired
     #[error("Access token has expired!")]
     AuthExpired,
 
+    /// Error returned when the access token is invalid for any reason other
+    /// than having expired, such as having been revoked
+    #[error("Bad access token: {0}")]
+    BadAccessToken(ApiError),
+
+    /// Error returned when the client doesn't have permission to access a
+    /// resource.
+    ///
+    /// This is usually because the user hasn't yet approved access in the
+    /// Monzo app.
+    #[error("Insufficient permissions: {0}")]
+    InsufficientPermissions(ApiError),
+
+    /// Error returned when the client is sending too many requests
+    #[error("Too many requests")]
+    TooManyRequests {
+        /// How long the API asked the client to wait before trying again
+        retry_after: Option<Duration>,
+    },
+
+    /// Error returned when a request is made with an invalid dedupe id
+    #[error("Invalid dedupe id: {0}")]
+    InvalidDedupeId(ApiError),
+
+    /// Any other error response returned by the Monzo API
+    #[error("API error: {0}")]
+    Api(ApiError),
+
+    /// Error returned when an amount of money can't be used for a request,
+    /// such as a pot deposit in a different currency to the pot
+    #[error("Invalid amount: {0}")]
+    InvalidAmount(#[from] crate::money::Error),
+
+    /// Error returned when the `state` returned by an `OAuth2` redirect doesn't
+    /// match the one that was sent
+    #[error("OAuth2 state mismatch")]
+    StateMismatch,
+
     /// API client errors
     #[error("Client error: {0}")]
     Client(StatusCode),
@@ -22,17 +82,210 @@ pub enum Error {
     /// API server errors
     #[error("Server error: {0}")]
     Server(StatusCode),
+
+    /// Error returned when uploading a file to the URL given by the API fails
+    ///
+    /// The upload goes to a storage service rather than the Monzo API, so the
+    /// status code says nothing about the client's access token.
+    #[error("Upload failed with status code {status}")]
+    Upload {
+        /// The status code of the response
+        status: StatusCode,
+
+        /// The response body
+        body: String,
+    },
+
+    /// Error returned when the API responds with a status code which isn't
+    /// expected, such as a redirect which couldn't be followed
+    #[error("Unexpected status code: {status}")]
+    UnexpectedStatus {
+        /// The status code of the response
+        status: StatusCode,
+
+        /// The target of the redirect, if there was one
+        location: Option<String>,
+    },
+}
+
+impl Error {
+    /// Construct an error from an unsuccessful API response.
+    ///
+    /// If the body contains a Monzo error, this is mapped to the appropriate
+    /// variant. Otherwise the error is constructed from the status code alone.
+    pub(crate) fn from_response(status_code: StatusCode, body: &[u8]) -> Self {
+        if !(status_code.is_client_error() || status_code.is_server_error())
+            || status_code == StatusCode::TOO_MANY_REQUESTS
+        {
+            return status_code.into();
+        }
+
+        serde_json::from_slice::<Envelope>(body).map_or_else(
+            |_| status_code.into(),
+            |envelope| ApiError::new(status_code, envelope).into(),
+        )
+    }
+
+    pub(crate) fn decode(
+        error: serde_path_to_error::Error<serde_json::Error>,
+        body: &[u8],
+    ) -> Self {
+        Self::Decode {
+            path: error.path().to_string(),
+            body: String::from_utf8_lossy(body).into_owned(),
+            source: error.into_inner(),
+        }
+    }
 }
 
 impl From<StatusCode> for Error {
     fn from(status_code: StatusCode) -> Self {
         if status_code == StatusCode::UNAUTHORIZED {
             return Self::AuthExpired;
+        } else if status_code == StatusCode::TOO_MANY_REQUESTS {
+            return Self::TooManyRequests { retry_after: None };
         } else if status_code.is_client_error() {
             return Self::Client(status_code);
         } else if status_code.is_server_error() {
             return Self::Server(status_code);
         }
-        unimplemented!()
+        Self::UnexpectedStatus {
+            status: status_code,
+            location: None,
+        }
+    }
+}
+
+impl From<ApiError> for Error {
+    fn from(error: ApiError) -> Self {
+        match error.code.as_str() {
+            "unauthorized.bad_access_token.expired" => Self::AuthExpired,
+            code if code.starts_with("unauthorized.bad_access_token") => {
+                Self::BadAccessToken(error)
+            }
+            "forbidden.insufficient_permissions" => Self::InsufficientPermissions(error),
+            "bad_request.invalid_dedupe_id" => Self::InvalidDedupeId(error),
+            _ => Self::Api(error),
+        }
+    }
+}
+
+/// An error response returned by the Monzo API
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ApiError {
+    /// The HTTP status code of the response
+    pub status: StatusCode,
+
+    /// A machine-readable error code, such as
+    /// `forbidden.insufficient_permissions`
+    pub code: String,
+
+    /// A human-readable description of the error
+    pub message: String,
+
+    /// Any additional parameters associated with the error
+    pub params: serde_json::Map<String, serde_json::Value>,
+}
+
+impl ApiError {
+    fn new(status: StatusCode, envelope: Envelope) -> Self {
+        Self {
+            status,
+            code: envelope.code,
+            message: envelope.message,
+            params: envelope.params,
+        }
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}): {}", self.status, self.code, self.message)
+    }
+}
+
+/// The JSON body of a Monzo error response
+#[derive(Deserialize)]
+struct Envelope {
+    code: String,
+
+    #[serde(default)]
+    message: String,
+
+    #[serde(default)]
+    params: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::StatusCode;
+    use test_case::test_case;
+
+    use super::Error;
+
+    fn error(status: StatusCode, code: &str) -> Error {
+        let body = format!(
+            r#"{{
+                "code": "{code}",
+                "message": "something went wrong",
+                "params": {{
+                    "client_id": "oauth2client_00009"
+                }}
+            }}"#
+        );
+        Error::from_response(status, body.as_bytes())
+    }
+
+    #[test_case(StatusCode::UNAUTHORIZED, "unauthorized.bad_access_token.expired" => matches Error::AuthExpired; "expired")]
+    #[test_case(StatusCode::UNAUTHORIZED, "unauthorized.bad_access_token.evicted" => matches Error::BadAccessToken(_); "evicted")]
+    #[test_case(StatusCode::UNAUTHORIZED, "unauthorized.bad_access_token" => matches Error::BadAccessToken(_); "bad access token")]
+    #[test_case(StatusCode::FORBIDDEN, "forbidden.insufficient_permissions" => matches Error::InsufficientPermissions(_); "insufficient permissions")]
+    #[test_case(StatusCode::BAD_REQUEST, "bad_request.invalid_dedupe_id" => matches Error::InvalidDedupeId(_); "invalid dedupe id")]
+    #[test_case(StatusCode::NOT_FOUND, "not_found.unknown_pot" => matches Error::Api(_); "other")]
+    fn from_api_error(status: StatusCode, code: &str) -> Error {
+        error(status, code)
+    }
+
+    #[test]
+    fn api_error_fields() {
+        let Error::Api(api_error) = error(StatusCode::NOT_FOUND, "not_found.unknown_pot") else {
+            panic!("expected an API error");
+        };
+
+        assert_eq!(api_error.status, StatusCode::NOT_FOUND);
+        assert_eq!(api_error.code, "not_found.unknown_pot");
+        assert_eq!(api_error.message, "something went wrong");
+        assert_eq!(api_error.params["client_id"], "oauth2client_00009");
+    }
+
+    #[test_case(StatusCode::UNAUTHORIZED => matches Error::AuthExpired; "unauthorized")]
+    #[test_case(StatusCode::NOT_FOUND => matches Error::Client(StatusCode::NOT_FOUND); "client")]
+    #[test_case(StatusCode::TOO_MANY_REQUESTS => matches Error::TooManyRequests { .. }; "too many requests")]
+    #[test_case(StatusCode::BAD_GATEWAY => matches Error::Server(StatusCode::BAD_GATEWAY); "server")]
+    #[test_case(StatusCode::SWITCHING_PROTOCOLS => matches Error::UnexpectedStatus { status: StatusCode::SWITCHING_PROTOCOLS, .. }; "informational")]
+    #[test_case(StatusCode::FOUND => matches Error::UnexpectedStatus { status: StatusCode::FOUND, .. }; "redirect")]
+    fn from_unstructured_body(status: StatusCode) -> Error {
+        Error::from_response(status, b"<html>Bad Gateway</html>")
+    }
+
+    #[test]
+    fn decode() {
+        #[derive(Debug, serde::Deserialize)]
+        struct Response {
+            #[allow(dead_code)]
+            items: Vec<u32>,
+        }
+
+        let body = br#"{"items": [1, 2, "three"]}"#;
+        let deserializer = &mut serde_json::Deserializer::from_slice(body);
+        let error = serde_path_to_error::deserialize::<_, Response>(deserializer).unwrap_err();
+
+        let Error::Decode { path, body, .. } = Error::decode(error, body) else {
+            panic!("expected a decode error");
+        };
+
+        assert_eq!(path, "items[2]");
+        assert_eq!(body, r#"{"items": [1, 2, "three"]}"#);
     }
 }
@@ -0,0 +1,58 @@
+//! Fields which aren't modelled by this crate
+
+use std::{
+    hash::{Hash, Hasher},
+    ops::{Deref, DerefMut},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// The fields of a JSON object which weren't recognised when it was
+/// deserialized
+///
+/// Models keep any fields which Monzo adds that this crate doesn't know about
+/// yet, so that no data is lost.
+///
+/// *This requires the `extra-fields` feature.*
+///
+/// # Example
+/// ```no_run
+/// # use monzo::Client;
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # let client = Client::new("ACCESS_TOKEN");
+/// let transaction = client.transaction("TRANSACTION_ID").send().await?;
+///
+/// for (key, value) in transaction.extra.iter() {
+///     println!("{key}: {value}");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Extra(pub serde_json::Map<String, serde_json::Value>);
+
+impl Deref for Extra {
+    type Target = serde_json::Map<String, serde_json::Value>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Extra {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+// JSON values can't be hashed, so only the keys are. Equal maps have equal
+// keys, so this is consistent with `Eq`.
+impl Hash for Extra {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for key in self.0.keys() {
+            key.hash(state);
+        }
+    }
+}
@@ -12,17 +12,25 @@
 
 mod client;
 #[doc(inline)]
-pub use client::Client;
+pub use client::{rate_limit, Client, ClientBuilder, RateLimiter, RetryPolicy};
 mod endpoints;
 #[doc(inline)]
 pub use endpoints::accounts::{Account, Owner};
 pub use endpoints::{
-    accounts, balance::Balance, feed_items, pots::Pot, transactions, transactions::Transaction,
-    who_am_i::Response as WhoAmI,
+    accounts, attachments, attachments::Attachment, auth::Authorization, balance, balance::Balance,
+    feed_items, pots, pots::Pot, receipts, receipts::Receipt, transactions,
+    transactions::Transaction, webhooks, webhooks::Webhook, who_am_i::Response as WhoAmI,
 };
 mod error;
+#[cfg(feature = "extra-fields")]
+mod extra;
+#[cfg(feature = "extra-fields")]
+pub use extra::Extra;
+pub mod money;
 pub use client::inner as inner_client;
-pub use error::Error;
+pub use error::{ApiError, Error};
+pub use money::{Currency, Money};
+pub mod token_store;
 
 /// Result type for all methods in this crate which can fail.
 pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,420 @@
+//! Amounts of money, and the currencies they're in
+//!
+//! The models returned by the API keep their amounts as `i64` fields in minor
+//! units, next to a single `currency` field, because that's the shape of the
+//! JSON: several amounts usually share one currency (eg. a [`Balance`]'s
+//! `balance`, `total_balance` and `spend_today`). Keeping the fields as they
+//! are means the models serialize back to the API's own representation. Each
+//! amount has a `*_money()` accessor which pairs it with its currency, such as
+//! [`Transaction::amount_money`].
+//!
+//! Requests which send money take a [`Money`], and check that it's in the
+//! right currency before anything is sent.
+//!
+//! [`Balance`]: crate::Balance
+//! [`Transaction::amount_money`]: crate::Transaction::amount_money
+
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// An amount of money in a particular currency
+///
+/// The amount is stored as an integer number of minor units (eg. pence for
+/// GBP), so there are no rounding errors. Arithmetic is checked, and refuses
+/// to mix currencies.
+///
+/// # Example
+/// ```
+/// use monzo::{Currency, Money};
+///
+/// let coffee = Money::new(350, Currency::GBP);
+/// let cake = Money::from_major(2, Currency::GBP).unwrap();
+///
+/// let total = coffee.checked_add(&cake)?;
+/// assert_eq!(total.to_string(), "£5.50");
+///
+/// let dollars = Money::new(100, Currency::USD);
+/// assert!(total.checked_add(&dollars).is_err());
+/// # Ok::<(), monzo::money::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Money {
+    /// The amount, in the minor units of the currency
+    pub minor_units: i64,
+
+    /// The currency of the amount
+    pub currency: Currency,
+}
+
+impl Money {
+    /// Create an amount from a number of minor units (eg. pence)
+    #[must_use]
+    pub const fn new(minor_units: i64, currency: Currency) -> Self {
+        Self {
+            minor_units,
+            currency,
+        }
+    }
+
+    /// Create an amount from a number of major units (eg. pounds)
+    ///
+    /// Returns `None` if the amount would overflow.
+    #[must_use]
+    pub fn from_major(major_units: i64, currency: Currency) -> Option<Self> {
+        let minor_units = major_units.checked_mul(10_i64.pow(currency.exponent()))?;
+        Some(Self::new(minor_units, currency))
+    }
+
+    /// An amount of zero in the given currency
+    #[must_use]
+    pub const fn zero(currency: Currency) -> Self {
+        Self::new(0, currency)
+    }
+
+    /// Whether the amount is negative
+    #[must_use]
+    pub const fn is_negative(&self) -> bool {
+        self.minor_units < 0
+    }
+
+    /// Add two amounts in the same currency
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the currencies differ, or the result would overflow.
+    pub fn checked_add(&self, other: &Self) -> Result<Self, Error> {
+        self.check_currency(other)?;
+        let minor_units = self
+            .minor_units
+            .checked_add(other.minor_units)
+            .ok_or(Error::Overflow)?;
+        Ok(Self::new(minor_units, self.currency.clone()))
+    }
+
+    /// Subtract an amount in the same currency
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the currencies differ, or the result would overflow.
+    pub fn checked_sub(&self, other: &Self) -> Result<Self, Error> {
+        self.check_currency(other)?;
+        let minor_units = self
+            .minor_units
+            .checked_sub(other.minor_units)
+            .ok_or(Error::Overflow)?;
+        Ok(Self::new(minor_units, self.currency.clone()))
+    }
+
+    /// Multiply the amount by a whole number
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the result would overflow.
+    pub fn checked_mul(&self, factor: i64) -> Result<Self, Error> {
+        let minor_units = self
+            .minor_units
+            .checked_mul(factor)
+            .ok_or(Error::Overflow)?;
+        Ok(Self::new(minor_units, self.currency.clone()))
+    }
+
+    /// Negate the amount
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the result would overflow.
+    pub fn checked_neg(&self) -> Result<Self, Error> {
+        let minor_units = self.minor_units.checked_neg().ok_or(Error::Overflow)?;
+        Ok(Self::new(minor_units, self.currency.clone()))
+    }
+
+    fn check_currency(&self, other: &Self) -> Result<(), Error> {
+        if self.currency == other.currency {
+            Ok(())
+        } else {
+            Err(Error::CurrencyMismatch(
+                self.currency.clone(),
+                other.currency.clone(),
+            ))
+        }
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.is_negative() { "-" } else { "" };
+        let exponent = self.currency.exponent();
+        let divisor = 10_u64.pow(exponent);
+        let major = self.minor_units.unsigned_abs() / divisor;
+        let minor = self.minor_units.unsigned_abs() % divisor;
+
+        let amount = if exponent == 0 {
+            major.to_string()
+        } else {
+            format!("{major}.{minor:0width$}", width = exponent as usize)
+        };
+
+        match self.currency.symbol() {
+            Some(symbol) => write!(f, "{sign}{symbol}{amount}"),
+            None => write!(f, "{sign}{amount} {}", self.currency),
+        }
+    }
+}
+
+/// An error from arithmetic on, or validation of, [`Money`]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// The amounts are in different currencies
+    #[error("can't combine amounts in {0} and {1}")]
+    CurrencyMismatch(Currency, Currency),
+
+    /// The result is too large to represent
+    #[error("arithmetic overflow")]
+    Overflow,
+
+    /// The amount is negative, where only a positive amount makes sense
+    #[error("amount can't be negative")]
+    Negative,
+}
+
+macro_rules! currencies {
+    ($($code:ident = $exponent:literal, $name:literal;)*) => {
+        /// An ISO 4217 currency
+        ///
+        /// Currencies which aren't listed here are represented by
+        /// [`Currency::Other`]. Parse codes with [`str::parse`] rather than
+        /// constructing [`Currency::Other`] directly; parsing ignores case,
+        /// and maps known codes to their own variants.
+        ///
+        /// Currencies compare by their code, ignoring case, so
+        /// `Currency::Other("GBP".to_string())` is equal to [`Currency::GBP`].
+        #[derive(Debug, Clone)]
+        #[non_exhaustive]
+        pub enum Currency {
+            $(
+                #[doc = $name]
+                $code,
+            )*
+
+            /// Any other currency, by its three letter code
+            Other(String),
+        }
+
+        impl Currency {
+            /// The three letter ISO 4217 code for the currency
+            #[must_use]
+            pub fn code(&self) -> &str {
+                match self {
+                    $(Self::$code => stringify!($code),)*
+                    Self::Other(code) => code,
+                }
+            }
+
+            /// The number of digits after the decimal point in the currency's
+            /// minor unit, eg. 2 for GBP (100 pence to the pound)
+            ///
+            /// This is assumed to be 2 for [`Currency::Other`].
+            #[must_use]
+            pub const fn exponent(&self) -> u32 {
+                match self {
+                    $(Self::$code => $exponent,)*
+                    Self::Other(_) => 2,
+                }
+            }
+        }
+
+        impl FromStr for Currency {
+            type Err = std::convert::Infallible;
+
+            fn from_str(code: &str) -> Result<Self, Self::Err> {
+                let code = code.to_ascii_uppercase();
+                Ok(match code.as_str() {
+                    $(stringify!($code) => Self::$code,)*
+                    _ => Self::Other(code),
+                })
+            }
+        }
+    };
+}
+
+currencies! {
+    AED = 2, "UAE dirham";
+    AUD = 2, "Australian dollar";
+    BHD = 3, "Bahraini dinar";
+    CAD = 2, "Canadian dollar";
+    CHF = 2, "Swiss franc";
+    CLP = 0, "Chilean peso";
+    CNY = 2, "Chinese yuan";
+    CZK = 2, "Czech koruna";
+    DKK = 2, "Danish krone";
+    EUR = 2, "Euro";
+    GBP = 2, "Pound sterling";
+    HKD = 2, "Hong Kong dollar";
+    HUF = 2, "Hungarian forint";
+    ISK = 0, "Icelandic króna";
+    INR = 2, "Indian rupee";
+    JOD = 3, "Jordanian dinar";
+    JPY = 0, "Japanese yen";
+    KRW = 0, "South Korean won";
+    KWD = 3, "Kuwaiti dinar";
+    MXN = 2, "Mexican peso";
+    NOK = 2, "Norwegian krone";
+    NZD = 2, "New Zealand dollar";
+    OMR = 3, "Omani rial";
+    PLN = 2, "Polish złoty";
+    SEK = 2, "Swedish krona";
+    SGD = 2, "Singapore dollar";
+    THB = 2, "Thai baht";
+    TND = 3, "Tunisian dinar";
+    TRY = 2, "Turkish lira";
+    USD = 2, "United States dollar";
+    VND = 0, "Vietnamese đồng";
+    ZAR = 2, "South African rand";
+}
+
+impl Currency {
+    const fn symbol(&self) -> Option<&'static str> {
+        match self {
+            Self::GBP => Some("£"),
+            Self::EUR => Some("€"),
+            Self::USD => Some("$"),
+            Self::JPY => Some("¥"),
+            _ => None,
+        }
+    }
+}
+
+impl PartialEq for Currency {
+    fn eq(&self, other: &Self) -> bool {
+        self.code().eq_ignore_ascii_case(other.code())
+    }
+}
+
+impl Eq for Currency {}
+
+impl Hash for Currency {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for byte in self.code().bytes() {
+            state.write_u8(byte.to_ascii_uppercase());
+        }
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl Serialize for Currency {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for Currency {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let code = String::deserialize(deserializer)?;
+        Ok(code.parse().unwrap_or_else(|never| match never {}))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::non_ascii_literal)]
+    use test_case::test_case;
+
+    use super::{Currency, Error, Money};
+
+    #[test_case(1234, Currency::GBP => "£12.34"; "pounds")]
+    #[test_case(-5, Currency::GBP => "-£0.05"; "negative pence")]
+    #[test_case(1000, Currency::EUR => "€10.00"; "euros")]
+    #[test_case(500, Currency::JPY => "¥500"; "no minor units")]
+    #[test_case(1234, Currency::KWD => "1.234 KWD"; "three decimal places")]
+    #[test_case(1234, Currency::Other("XYZ".to_string()) => "12.34 XYZ"; "unknown currency")]
+    fn display(minor_units: i64, currency: Currency) -> String {
+        Money::new(minor_units, currency).to_string()
+    }
+
+    #[test]
+    fn from_major() {
+        assert_eq!(
+            Money::from_major(12, Currency::GBP),
+            Some(Money::new(1200, Currency::GBP))
+        );
+        assert_eq!(
+            Money::from_major(12, Currency::JPY),
+            Some(Money::new(12, Currency::JPY))
+        );
+        assert_eq!(Money::from_major(i64::MAX, Currency::GBP), None);
+    }
+
+    #[test]
+    fn checked_arithmetic() {
+        let a = Money::new(1000, Currency::GBP);
+        let b = Money::new(250, Currency::GBP);
+
+        assert_eq!(a.checked_add(&b), Ok(Money::new(1250, Currency::GBP)));
+        assert_eq!(a.checked_sub(&b), Ok(Money::new(750, Currency::GBP)));
+        assert_eq!(b.checked_mul(3), Ok(Money::new(750, Currency::GBP)));
+        assert_eq!(
+            Money::new(i64::MAX, Currency::GBP).checked_add(&b),
+            Err(Error::Overflow)
+        );
+    }
+
+    #[test]
+    fn mixed_currencies() {
+        let pounds = Money::new(1000, Currency::GBP);
+        let euros = Money::new(1000, Currency::EUR);
+
+        assert_eq!(
+            pounds.checked_add(&euros),
+            Err(Error::CurrencyMismatch(Currency::GBP, Currency::EUR))
+        );
+    }
+
+    #[test]
+    fn currency_serde() {
+        let currency: Currency = serde_json::from_str(r#""GBP""#).unwrap();
+        assert_eq!(currency, Currency::GBP);
+
+        let currency: Currency = serde_json::from_str(r#""XYZ""#).unwrap();
+        assert_eq!(currency, Currency::Other("XYZ".to_string()));
+        assert_eq!(serde_json::to_string(&currency).unwrap(), r#""XYZ""#);
+
+        let currency: Currency = serde_json::from_str(r#""gbp""#).unwrap();
+        assert!(matches!(currency, Currency::GBP));
+    }
+
+    #[test]
+    fn currency_parse() {
+        assert!(matches!("GBP".parse(), Ok(Currency::GBP)));
+        assert!(matches!("gbp".parse(), Ok(Currency::GBP)));
+        assert!(matches!("xyz".parse(), Ok(Currency::Other(code)) if code == "XYZ"));
+    }
+
+    #[test]
+    fn currency_eq() {
+        use std::collections::HashSet;
+
+        let other = Currency::Other("gbp".to_string());
+        assert_eq!(other, Currency::GBP);
+        assert_ne!(Currency::Other("XYZ".to_string()), Currency::GBP);
+
+        let set: HashSet<_> = [Currency::GBP, other].into_iter().collect();
+        assert_eq!(set.len(), 1);
+    }
+}
@@ -0,0 +1,275 @@
+//! Persistent storage for authentication tokens
+//!
+//! Monzo refresh tokens can only be used once, so a
+//! [`Client<Refreshable>`](crate::Client) which refreshes its own tokens should
+//! persist each new pair as soon as it is issued. Otherwise a crash after a
+//! refresh leaves only an invalidated refresh token behind.
+
+use std::{
+    fmt::Debug,
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::Result;
+
+/// A set of authentication tokens
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Tokens {
+    /// Access token for authorising requests against the Monzo API
+    pub access_token: String,
+
+    /// Single-use token for generating a new access/refresh token pair
+    pub refresh_token: String,
+
+    /// The time at which the access token expires, if known
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// A place to persist authentication tokens
+///
+/// A [`Client<Refreshable>`](crate::Client) calls [`TokenStore::save`] each
+/// time its tokens are rotated. The methods are blocking, so the client calls
+/// `save` on tokio's blocking thread pool rather than on an async task.
+pub trait TokenStore: Send + Sync + Debug {
+    /// Load the most recently saved tokens
+    fn load(&self) -> Result<Tokens>;
+
+    /// Persist a newly issued set of tokens
+    fn save(&self, tokens: &Tokens) -> Result<()>;
+}
+
+/// A shared store, so that the caller can keep a handle to the store they give
+/// to a client
+impl<T: TokenStore + ?Sized> TokenStore for Arc<T> {
+    fn load(&self) -> Result<Tokens> {
+        (**self).load()
+    }
+
+    fn save(&self, tokens: &Tokens) -> Result<()> {
+        (**self).save(tokens)
+    }
+}
+
+/// A [`TokenStore`] which only keeps tokens in memory.
+///
+/// This is mostly useful for testing, since the tokens are lost when the
+/// process exits.
+#[derive(Debug)]
+pub struct InMemory {
+    tokens: Mutex<Tokens>,
+}
+
+impl InMemory {
+    /// Create a new in-memory store, holding the given tokens
+    #[must_use]
+    pub const fn new(tokens: Tokens) -> Self {
+        Self {
+            tokens: Mutex::new(tokens),
+        }
+    }
+}
+
+impl TokenStore for InMemory {
+    fn load(&self) -> Result<Tokens> {
+        Ok(self
+            .tokens
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone())
+    }
+
+    fn save(&self, tokens: &Tokens) -> Result<()> {
+        tokens.clone_into(&mut self.tokens.lock().unwrap_or_else(PoisonError::into_inner));
+        Ok(())
+    }
+}
+
+/// A [`TokenStore`] which keeps tokens in a JSON file.
+///
+/// Tokens are written to a temporary file which is then renamed over the
+/// original, so the file is never left partially written. Both the file and
+/// its directory are synced to disk before `save` returns, so a crash can't
+/// leave an old (already invalidated) refresh token behind. On Unix, the file
+/// can only be read by the current user.
+#[derive(Debug, Clone)]
+pub struct JsonFile {
+    path: PathBuf,
+}
+
+impl JsonFile {
+    /// Create a store backed by the file at the given path
+    #[must_use]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// The path of the backing file
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// A unique path in the same directory as the backing file, so that
+    /// concurrent saves don't write to the same temporary file
+    fn temp_path(&self) -> PathBuf {
+        use rand::{distr::Alphanumeric, rng, RngExt};
+
+        let suffix: String = rng()
+            .sample_iter(&Alphanumeric)
+            .map(char::from)
+            .take(12)
+            .collect();
+
+        let mut file_name = self.path.file_name().unwrap_or_default().to_owned();
+        file_name.push(format!(".{suffix}.tmp"));
+        self.path.with_file_name(file_name)
+    }
+}
+
+fn write_and_rename(temp_path: &Path, path: &Path, tokens: &Tokens) -> Result<()> {
+    let mut file = create_private(temp_path)?;
+    serde_json::to_writer_pretty(&mut file, tokens)?;
+    file.flush()?;
+    file.sync_all()?;
+
+    fs::rename(temp_path, path)?;
+    sync_parent_dir(path)?;
+    Ok(())
+}
+
+/// Sync the directory containing `path`, so that a rename into it is durable
+#[cfg(unix)]
+fn sync_parent_dir(path: &Path) -> std::io::Result<()> {
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    fs::File::open(parent)?.sync_all()
+}
+
+// directories can't be opened as files on other platforms, where the rename
+// itself is as durable as it gets
+#[cfg(not(unix))]
+#[allow(clippy::unnecessary_wraps)]
+const fn sync_parent_dir(_path: &Path) -> std::io::Result<()> {
+    Ok(())
+}
+
+/// Create a new file which only the current user can read or write
+fn create_private(path: &Path) -> std::io::Result<fs::File> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    options.open(path)
+}
+
+impl TokenStore for JsonFile {
+    fn load(&self) -> Result<Tokens> {
+        let contents = fs::read(&self.path)?;
+        Ok(serde_json::from_slice(&contents)?)
+    }
+
+    fn save(&self, tokens: &Tokens) -> Result<()> {
+        let temp_path = self.temp_path();
+
+        let result = write_and_rename(&temp_path, &self.path, tokens);
+
+        if result.is_err() {
+            // best effort, the original error is more useful
+            fs::remove_file(&temp_path).ok();
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::{InMemory, JsonFile, TokenStore, Tokens};
+
+    fn tokens(suffix: &str) -> Tokens {
+        Tokens {
+            access_token: format!("access_{suffix}"),
+            refresh_token: format!("refresh_{suffix}"),
+            expires_at: Some(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()),
+        }
+    }
+
+    #[test]
+    fn in_memory() {
+        let store = InMemory::new(tokens("1"));
+        assert_eq!(store.load().unwrap(), tokens("1"));
+
+        store.save(&tokens("2")).unwrap();
+        assert_eq!(store.load().unwrap(), tokens("2"));
+    }
+
+    #[test]
+    fn json_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = JsonFile::new(dir.path().join("tokens.json"));
+
+        assert!(store.load().is_err());
+
+        store.save(&tokens("1")).unwrap();
+        assert_eq!(store.load().unwrap(), tokens("1"));
+
+        store.save(&tokens("2")).unwrap();
+        assert_eq!(store.load().unwrap(), tokens("2"));
+
+        // the temporary file has been renamed over the original
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn json_file_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let store = JsonFile::new(dir.path().join("tokens.json"));
+        store.save(&tokens("1")).unwrap();
+
+        let mode = std::fs::metadata(store.path())
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn json_file_concurrent_saves() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = JsonFile::new(dir.path().join("tokens.json"));
+
+        std::thread::scope(|scope| {
+            for i in 0..8 {
+                let store = &store;
+                scope.spawn(move || {
+                    for _ in 0..10 {
+                        store.save(&tokens(&i.to_string())).unwrap();
+                    }
+                });
+            }
+        });
+
+        // whichever save finished last, the file is intact
+        assert!(store.load().is_ok());
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+}
@@ -0,0 +1,106 @@
+use monzo::{accounts::Kind, Client};
+use serde_json::{json, Value};
+use wiremock::{
+    matchers::{method, path, query_param, query_param_is_missing},
+    Mock, MockServer, ResponseTemplate,
+};
+
+fn account(id: &str, closed: bool, owners: &[&str]) -> Value {
+    let owners: Vec<_> = owners
+        .iter()
+        .map(|user_id| {
+            json!({
+                "user_id": user_id,
+                "preferred_name": "First Last",
+                "preferred_first_name": "First"
+            })
+        })
+        .collect();
+
+    json!({
+        "id": id,
+        "closed": closed,
+        "created": "2024-01-01T00:00:00.000Z",
+        "description": "description",
+        "type": "uk_retail_joint",
+        "currency": "GBP",
+        "country_code": "GB",
+        "owners": owners,
+        "account_number": "12345678",
+        "sort_code": "040004"
+    })
+}
+
+fn accounts() -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(json!({
+        "accounts": [
+            account("acc_1", false, &["user_1", "user_2"]),
+            account("acc_2", true, &["user_1", "user_2"]),
+            account("acc_3", false, &["user_2", "user_3"]),
+        ]
+    }))
+}
+
+#[tokio::test]
+async fn closed_accounts_are_excluded() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/accounts"))
+        .and(query_param_is_missing("account_type"))
+        .respond_with(accounts())
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let client = Client::builder().url(server.uri()).build("ACCESS_TOKEN");
+
+    let ids: Vec<_> = client
+        .accounts()
+        .send()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|account| account.id)
+        .collect();
+    assert_eq!(ids, ["acc_1", "acc_3"]);
+
+    let ids: Vec<_> = client
+        .accounts()
+        .include_closed(true)
+        .send()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|account| account.id)
+        .collect();
+    assert_eq!(ids, ["acc_1", "acc_2", "acc_3"]);
+}
+
+#[tokio::test]
+async fn filters() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/accounts"))
+        .and(query_param("account_type", "uk_retail_joint"))
+        .respond_with(accounts())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Client::builder().url(server.uri()).build("ACCESS_TOKEN");
+
+    let accounts = client
+        .accounts()
+        .account_type(Kind::UkRetailJoint)
+        .owned_by("user_1")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(accounts.len(), 1);
+    assert_eq!(accounts[0].id, "acc_1");
+    assert!(accounts[0].is_joint());
+    assert_eq!(accounts[0].sort_code(), Some("040004"));
+}
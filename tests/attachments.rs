@@ -0,0 +1,159 @@
+use std::time::Duration;
+
+use monzo::{Client, Error};
+use reqwest::StatusCode;
+use serde_json::json;
+use wiremock::{
+    matchers::{body_string, header, method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+#[tokio::test]
+async fn upload_attachment() {
+    let server = MockServer::start().await;
+    let file_url = format!("{}/files/receipt.png", server.uri());
+
+    Mock::given(method("POST"))
+        .and(path("/attachment/upload"))
+        .and(body_string(
+            "file_name=receipt.png&file_type=image%2Fpng&content_length=4",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "file_url": file_url,
+            "upload_url": format!("{}/upload/receipt.png", server.uri()),
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("PUT"))
+        .and(path("/upload/receipt.png"))
+        .and(header("content-type", "image/png"))
+        .and(body_string("data"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/attachment/register"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "attachment": {
+                "id": "attach_0000",
+                "user_id": "user_0000",
+                "external_id": "tx_0000",
+                "file_url": file_url,
+                "file_type": "image/png",
+                "created": "2024-01-01T00:00:00Z"
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Client::builder().url(server.uri()).build("ACCESS_TOKEN");
+
+    let attachment = client
+        .upload_attachment("tx_0000", "receipt.png", "image/png", "data".into())
+        .await
+        .unwrap();
+
+    assert_eq!(attachment.external_id, "tx_0000");
+    assert_eq!(attachment.file_url, file_url);
+}
+
+async fn mock_upload_url(server: &MockServer) {
+    Mock::given(method("POST"))
+        .and(path("/attachment/upload"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "file_url": format!("{}/files/receipt.png", server.uri()),
+            "upload_url": format!("{}/upload/receipt.png", server.uri()),
+        })))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn failed_upload() {
+    let server = MockServer::start().await;
+    mock_upload_url(&server).await;
+
+    Mock::given(method("PUT"))
+        .and(path("/upload/receipt.png"))
+        .respond_with(ResponseTemplate::new(403).set_body_string("<Error>AccessDenied</Error>"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // a failed upload isn't mistaken for an expired access token
+    Mock::given(path("/oauth2/token"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    Mock::given(path("/attachment/register"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let client = Client::builder().url(server.uri()).build_refreshable(
+        "ACCESS_TOKEN",
+        "CLIENT_ID",
+        "CLIENT_SECRET",
+        "REFRESH_TOKEN",
+    );
+
+    let error = client
+        .upload_attachment("tx_0000", "receipt.png", "image/png", "data".into())
+        .await
+        .unwrap_err();
+
+    let Error::Upload { status, body } = error else {
+        panic!("expected an upload error, got {error:?}");
+    };
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body, "<Error>AccessDenied</Error>");
+}
+
+#[tokio::test]
+async fn upload_timeout() {
+    let server = MockServer::start().await;
+    mock_upload_url(&server).await;
+
+    Mock::given(method("PUT"))
+        .and(path("/upload/receipt.png"))
+        .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(5)))
+        .mount(&server)
+        .await;
+
+    let client = Client::builder()
+        .url(server.uri())
+        .timeout(Duration::from_millis(100))
+        .build("ACCESS_TOKEN");
+
+    let error = client
+        .upload_attachment("tx_0000", "receipt.png", "image/png", "data".into())
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, Error::Http(e) if e.is_timeout()));
+}
+
+#[tokio::test]
+async fn deregister_attachment() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/attachment/deregister"))
+        .and(body_string("id=attach_0000"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Client::builder().url(server.uri()).build("ACCESS_TOKEN");
+
+    client.deregister_attachment("attach_0000").await.unwrap();
+}
@@ -0,0 +1,411 @@
+use std::{sync::Arc, time::Duration};
+
+use monzo::{
+    inner_client::Refreshable,
+    token_store::{InMemory, TokenStore, Tokens},
+    Client, Error, RetryPolicy,
+};
+use reqwest::header::HeaderValue;
+use serde_json::json;
+use wiremock::{
+    matchers::{body_string_contains, header, method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+fn who_am_i() -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(json!({
+        "authenticated": true,
+        "client_id": "oauth2client_0000",
+        "user_id": "user_0000"
+    }))
+}
+
+fn expired() -> ResponseTemplate {
+    ResponseTemplate::new(401).set_body_json(json!({
+        "code": "unauthorized.bad_access_token.expired",
+        "message": "Access token has expired"
+    }))
+}
+
+fn tokens() -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(json!({
+        "access_token": "ACCESS_TOKEN_2",
+        "client_id": "oauth2client_0000",
+        "expires_in": 21600,
+        "refresh_token": "REFRESH_TOKEN_2",
+        "token_type": "Bearer",
+        "user_id": "user_0000"
+    }))
+}
+
+#[tokio::test]
+async fn builder() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/ping/whoami"))
+        .and(header("authorization", "Bearer ACCESS_TOKEN"))
+        .and(header("user-agent", "monzo-test/1.0"))
+        .respond_with(who_am_i())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Client::builder()
+        .url(server.uri())
+        .timeout(Duration::from_secs(5))
+        .user_agent(HeaderValue::from_static("monzo-test/1.0"))
+        .build("ACCESS_TOKEN");
+
+    let response = client.who_am_i().await.unwrap();
+    assert_eq!(response.user_id, "user_0000");
+}
+
+#[tokio::test]
+async fn refresh_expired_token() {
+    let server = MockServer::start().await;
+
+    Mock::given(path("/ping/whoami"))
+        .and(header("authorization", "Bearer ACCESS_TOKEN_1"))
+        .respond_with(expired())
+        .mount(&server)
+        .await;
+
+    Mock::given(path("/ping/whoami"))
+        .and(header("authorization", "Bearer ACCESS_TOKEN_2"))
+        .respond_with(who_am_i())
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/oauth2/token"))
+        .and(body_string_contains("refresh_token=REFRESH_TOKEN_1"))
+        .respond_with(tokens())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Client::builder().url(server.uri()).build_refreshable(
+        "ACCESS_TOKEN_1",
+        "CLIENT_ID",
+        "CLIENT_SECRET",
+        "REFRESH_TOKEN_1",
+    );
+
+    // concurrent requests share a single refresh
+    let (a, b, c) = tokio::join!(client.who_am_i(), client.who_am_i(), client.who_am_i());
+    a.unwrap();
+    b.unwrap();
+    c.unwrap();
+
+    assert_eq!(client.access_token(), "ACCESS_TOKEN_2");
+    assert_eq!(client.refresh_token(), "REFRESH_TOKEN_2");
+    assert!(client.expires_at().is_some());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn concurrent_refresh_is_single_flight() {
+    let server = MockServer::start().await;
+
+    Mock::given(path("/ping/whoami"))
+        .and(header("authorization", "Bearer ACCESS_TOKEN_1"))
+        .respond_with(expired())
+        .mount(&server)
+        .await;
+
+    Mock::given(path("/ping/whoami"))
+        .and(header("authorization", "Bearer ACCESS_TOKEN_2"))
+        .respond_with(who_am_i())
+        .expect(8)
+        .mount(&server)
+        .await;
+
+    // a slow token endpoint, so that every task sees the expired token before
+    // the refresh completes
+    Mock::given(method("POST"))
+        .and(path("/oauth2/token"))
+        .respond_with(tokens().set_delay(Duration::from_millis(200)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Client::builder().url(server.uri()).build_refreshable(
+        "ACCESS_TOKEN_1",
+        "CLIENT_ID",
+        "CLIENT_SECRET",
+        "REFRESH_TOKEN_1",
+    );
+
+    let tasks: Vec<_> = (0..8)
+        .map(|_| {
+            let client = client.clone();
+            tokio::spawn(async move { client.who_am_i().await })
+        })
+        .collect();
+
+    for task in tasks {
+        task.await.unwrap().unwrap();
+    }
+
+    assert_eq!(client.access_token(), "ACCESS_TOKEN_2");
+}
+
+#[tokio::test]
+async fn refresh_before_expiry() {
+    let server = MockServer::start().await;
+
+    Mock::given(path("/ping/whoami"))
+        .and(header("authorization", "Bearer ACCESS_TOKEN_2"))
+        .respond_with(who_am_i())
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/oauth2/token"))
+        .respond_with(tokens())
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    // with a margin longer than the token lifetime, every request refreshes
+    let client = Client::builder()
+        .url(server.uri())
+        .build_refreshable(
+            "ACCESS_TOKEN_1",
+            "CLIENT_ID",
+            "CLIENT_SECRET",
+            "REFRESH_TOKEN_1",
+        )
+        .with_refresh_margin(chrono::TimeDelta::days(1));
+
+    client.refresh_auth().await.unwrap();
+    client.who_am_i().await.unwrap();
+}
+
+fn client_expiring_in(
+    server: &MockServer,
+    expires_in: chrono::TimeDelta,
+    refresh_margin: chrono::TimeDelta,
+) -> Client<Refreshable> {
+    let tokens = Tokens {
+        access_token: "ACCESS_TOKEN_1".to_string(),
+        refresh_token: "REFRESH_TOKEN_1".to_string(),
+        expires_at: Some(chrono::Utc::now() + expires_in),
+    };
+
+    Client::builder()
+        .url(server.uri())
+        .build_from_token_store("CLIENT_ID", "CLIENT_SECRET", InMemory::new(tokens))
+        .unwrap()
+        .with_refresh_margin(refresh_margin)
+}
+
+#[tokio::test]
+async fn refresh_inside_margin() {
+    let server = MockServer::start().await;
+
+    Mock::given(path("/ping/whoami"))
+        .and(header("authorization", "Bearer ACCESS_TOKEN_2"))
+        .respond_with(who_am_i())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/oauth2/token"))
+        .respond_with(tokens())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = client_expiring_in(
+        &server,
+        chrono::TimeDelta::minutes(4),
+        chrono::TimeDelta::minutes(5),
+    );
+
+    client.who_am_i().await.unwrap();
+}
+
+#[tokio::test]
+async fn no_refresh_outside_margin() {
+    let server = MockServer::start().await;
+
+    Mock::given(path("/ping/whoami"))
+        .and(header("authorization", "Bearer ACCESS_TOKEN_1"))
+        .respond_with(who_am_i())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/oauth2/token"))
+        .respond_with(tokens())
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let client = client_expiring_in(
+        &server,
+        chrono::TimeDelta::minutes(6),
+        chrono::TimeDelta::minutes(5),
+    );
+
+    client.who_am_i().await.unwrap();
+}
+
+#[tokio::test]
+async fn authorization_code_exchange() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/oauth2/token"))
+        .and(body_string_contains("grant_type=authorization_code"))
+        .and(body_string_contains("code=AUTH_CODE"))
+        .and(body_string_contains(
+            "redirect_uri=http%3A%2F%2Flocalhost%3A8080%2Fcallback",
+        ))
+        .respond_with(tokens())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let authorization = Client::builder().url(server.uri()).authorize(
+        "CLIENT_ID",
+        "CLIENT_SECRET",
+        "http://localhost:8080/callback",
+    );
+    let state = authorization.state().to_string();
+
+    let client = authorization.exchange("AUTH_CODE", &state).await.unwrap();
+
+    assert_eq!(client.access_token(), "ACCESS_TOKEN_2");
+    assert_eq!(client.refresh_token(), "REFRESH_TOKEN_2");
+    assert!(client.expires_at().is_some());
+}
+
+#[tokio::test]
+async fn authorization_state_mismatch() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/oauth2/token"))
+        .respond_with(tokens())
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let authorization = Client::builder().url(server.uri()).authorize(
+        "CLIENT_ID",
+        "CLIENT_SECRET",
+        "http://localhost:8080/callback",
+    );
+
+    let error = authorization
+        .exchange("AUTH_CODE", "forged")
+        .await
+        .unwrap_err();
+    assert!(matches!(error, Error::StateMismatch));
+}
+
+#[tokio::test]
+async fn refresh_saves_tokens() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/oauth2/token"))
+        .and(body_string_contains("refresh_token=REFRESH_TOKEN_1"))
+        .respond_with(tokens())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let store = Arc::new(InMemory::new(Tokens {
+        access_token: "ACCESS_TOKEN_1".to_string(),
+        refresh_token: "REFRESH_TOKEN_1".to_string(),
+        expires_at: None,
+    }));
+
+    let client = Client::builder()
+        .url(server.uri())
+        .build_from_token_store("CLIENT_ID", "CLIENT_SECRET", Arc::clone(&store))
+        .unwrap();
+
+    client.refresh_auth().await.unwrap();
+
+    let saved = store.load().unwrap();
+    assert_eq!(saved.access_token, "ACCESS_TOKEN_2");
+    assert_eq!(saved.refresh_token, "REFRESH_TOKEN_2");
+    assert!(saved.expires_at.is_some());
+}
+
+#[tokio::test]
+async fn out_of_range_expiry_is_unknown() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/oauth2/token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "access_token": "ACCESS_TOKEN_2",
+            "client_id": "oauth2client_0000",
+            "expires_in": i64::MAX,
+            "refresh_token": "REFRESH_TOKEN_2",
+            "token_type": "Bearer",
+            "user_id": "user_0000"
+        })))
+        .mount(&server)
+        .await;
+
+    let client = Client::builder().url(server.uri()).build_refreshable(
+        "ACCESS_TOKEN_1",
+        "CLIENT_ID",
+        "CLIENT_SECRET",
+        "REFRESH_TOKEN_1",
+    );
+
+    client.refresh_auth().await.unwrap();
+    assert_eq!(client.expires_at(), None);
+}
+
+#[tokio::test]
+async fn retry_server_errors() {
+    let server = MockServer::start().await;
+
+    Mock::given(path("/ping/whoami"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(2)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(path("/ping/whoami"))
+        .respond_with(who_am_i())
+        .mount(&server)
+        .await;
+
+    let client = Client::builder()
+        .url(server.uri())
+        .retry_policy(RetryPolicy::new(2).initial_backoff(Duration::from_millis(1)))
+        .build("ACCESS_TOKEN");
+
+    client.who_am_i().await.unwrap();
+}
+
+#[tokio::test]
+async fn structured_errors() {
+    let server = MockServer::start().await;
+
+    Mock::given(path("/ping/whoami"))
+        .respond_with(ResponseTemplate::new(403).set_body_json(json!({
+            "code": "forbidden.insufficient_permissions",
+            "message": "Access forbidden due to insufficient permissions",
+            "params": {}
+        })))
+        .mount(&server)
+        .await;
+
+    let client = Client::builder().url(server.uri()).build("ACCESS_TOKEN");
+
+    let error = client.who_am_i().await.unwrap_err();
+    assert!(matches!(error, Error::InsufficientPermissions(_)));
+}
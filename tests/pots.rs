@@ -0,0 +1,230 @@
+use monzo::{money, Account, Client, Currency, Error, Money, Pot};
+use serde_json::{json, Value};
+use wiremock::{
+    matchers::{body_string, method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+fn pot_json(name: &str, goal_amount: u32, deleted: bool) -> Value {
+    json!({
+        "id": "pot_0000",
+        "name": name,
+        "style": "",
+        "balance": 0,
+        "currency": "GBP",
+        "goal_amount": goal_amount,
+        "current_account_id": "acc_0000",
+        "created": "2026-04-06T00:00:00Z",
+        "updated": "2026-04-06T00:00:00Z",
+        "deleted": deleted
+    })
+}
+
+fn pot(name: &str, goal_amount: u32, deleted: bool) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(pot_json(name, goal_amount, deleted))
+}
+
+fn existing_pot() -> Pot {
+    serde_json::from_value(pot_json("Holiday", 60000, false)).unwrap()
+}
+
+fn account() -> Account {
+    serde_json::from_value(json!({
+        "id": "acc_0000",
+        "closed": false,
+        "created": "2024-01-01T00:00:00.000Z",
+        "description": "description",
+        "type": "uk_retail",
+        "currency": "GBP",
+        "country_code": "GB",
+        "owners": [],
+        "account_number": "12345678",
+        "sort_code": "040004"
+    }))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn create_pot() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/pots/create"))
+        .and(body_string(
+            "current_account_id=acc_0000&name=Car+insurance&goal_amount=60000",
+        ))
+        .respond_with(pot("Car insurance", 60000, false))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Client::builder().url(server.uri()).build("ACCESS_TOKEN");
+
+    let pot = client
+        .create_pot(&account(), "Car insurance")
+        .goal_amount(&Money::new(60000, Currency::GBP))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(pot.name, "Car insurance");
+    assert_eq!(pot.goal_amount, Some(60000));
+}
+
+#[tokio::test]
+async fn update_pot() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("PATCH"))
+        .and(path("/pots/pot_0000"))
+        .and(body_string("name=Holiday"))
+        .respond_with(pot("Holiday", 60000, false))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Client::builder().url(server.uri()).build("ACCESS_TOKEN");
+
+    let pot = client
+        .update_pot(&existing_pot())
+        .name("Holiday")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(pot.name, "Holiday");
+}
+
+#[tokio::test]
+async fn delete_pot() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/pots/pot_0000"))
+        .respond_with(pot("Holiday", 60000, true))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Client::builder().url(server.uri()).build("ACCESS_TOKEN");
+
+    let pot = client.delete_pot("pot_0000").await.unwrap();
+
+    assert!(pot.deleted);
+}
+
+#[tokio::test]
+async fn deposit_with_operation_id() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/pots/pot_0000/deposit"))
+        .and(body_string(
+            "source_account_id=acc_0000&amount=1000&dedupe_id=op_1%3Adeposit%3Apot_0000",
+        ))
+        .respond_with(pot("Holiday", 60000, false))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Client::builder().url(server.uri()).build("ACCESS_TOKEN");
+    let pot = existing_pot();
+
+    let request = client
+        .deposit_into_pot(&pot, "acc_0000", &Money::new(1000, Currency::GBP))
+        .with_operation_id("op_1");
+    assert_eq!(request.dedupe_id(), "op_1:deposit:pot_0000");
+
+    request.send().await.unwrap();
+}
+
+#[tokio::test]
+async fn withdraw_with_dedupe_id() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/pots/pot_0000/withdraw"))
+        .and(body_string(
+            "destination_account_id=acc_0000&amount=1000&dedupe_id=withdrawal-1",
+        ))
+        .respond_with(pot("Holiday", 60000, false))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Client::builder().url(server.uri()).build("ACCESS_TOKEN");
+    let pot = existing_pot();
+
+    let request = client
+        .withdraw_from_pot(&pot, "acc_0000", &Money::new(1000, Currency::GBP))
+        .with_dedupe_id("withdrawal-1");
+    assert_eq!(request.dedupe_id(), "withdrawal-1");
+
+    request.send().await.unwrap();
+}
+
+#[tokio::test]
+async fn invalid_amounts_are_not_sent() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .respond_with(pot("Holiday", 60000, false))
+        .expect(0)
+        .mount(&server)
+        .await;
+    Mock::given(method("PATCH"))
+        .respond_with(pot("Holiday", 60000, false))
+        .expect(0)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .respond_with(pot("Holiday", 60000, false))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let client = Client::builder().url(server.uri()).build("ACCESS_TOKEN");
+    let pot = existing_pot();
+
+    let error = client
+        .deposit_into_pot(&pot, "acc_0000", &Money::new(1000, Currency::EUR))
+        .send()
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        Error::InvalidAmount(money::Error::CurrencyMismatch(Currency::GBP, Currency::EUR))
+    ));
+
+    let error = client
+        .withdraw_from_pot(&pot, "acc_0000", &Money::new(-1000, Currency::GBP))
+        .send()
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        Error::InvalidAmount(money::Error::Negative)
+    ));
+
+    let error = client
+        .update_pot(&pot)
+        .goal_amount(&Money::new(1000, Currency::USD))
+        .send()
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        Error::InvalidAmount(money::Error::CurrencyMismatch(..))
+    ));
+
+    let error = client
+        .create_pot(&account(), "Car insurance")
+        .goal_amount(&Money::new(-1, Currency::GBP))
+        .send()
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        Error::InvalidAmount(money::Error::Negative)
+    ));
+}
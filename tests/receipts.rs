@@ -0,0 +1,126 @@
+use monzo::{
+    receipts::{InvalidReceipt, Item, Receipt},
+    Client, Currency, Money,
+};
+use serde_json::json;
+use wiremock::{
+    matchers::{body_json, method, path, query_param},
+    Mock, MockServer, ResponseTemplate,
+};
+
+fn receipt() -> serde_json::Value {
+    json!({
+        "transaction_id": "tx_0000",
+        "external_id": "receipt_0000",
+        "total": 550,
+        "currency": "GBP",
+        "items": [
+            { "description": "Coffee", "quantity": 1.0, "amount": 300, "currency": "GBP" },
+            { "description": "Croissant", "quantity": 1.0, "amount": 250, "currency": "GBP" }
+        ]
+    })
+}
+
+#[tokio::test]
+async fn upload_receipt() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/transaction-receipts"))
+        .and(body_json(receipt()))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Client::builder().url(server.uri()).build("ACCESS_TOKEN");
+
+    let receipt = Receipt::builder("tx_0000", "receipt_0000", Money::new(550, Currency::GBP))
+        .item(Item::new("Coffee", Money::new(300, Currency::GBP)))
+        .item(Item::new("Croissant", Money::new(250, Currency::GBP)))
+        .build()
+        .unwrap();
+
+    client.upload_receipt(&receipt).await.unwrap();
+}
+
+#[tokio::test]
+async fn receipt_for_transaction() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/transactions/tx_0000"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "tx_0000",
+            "account_id": "acc_0000",
+            "amount": -550,
+            "amount_is_pending": false,
+            "can_add_to_tab": false,
+            "can_be_excluded_from_breakdown": false,
+            "can_be_made_subscription": false,
+            "can_split_the_bill": false,
+            "category": "eating_out",
+            "created": "2024-01-01T00:00:00Z",
+            "currency": "GBP",
+            "description": "CAFE",
+            "include_in_spending": true,
+            "merchant": null,
+            "metadata": {},
+            "notes": "",
+            "is_load": false,
+            "settled": ""
+        })))
+        .mount(&server)
+        .await;
+
+    let client = Client::builder().url(server.uri()).build("ACCESS_TOKEN");
+    let transaction = client.transaction("tx_0000").send().await.unwrap();
+
+    let receipt = Receipt::for_transaction(&transaction, "receipt_0000")
+        .item(Item::new("Coffee", Money::new(300, Currency::GBP)))
+        .item(Item::new("Croissant", Money::new(250, Currency::GBP)))
+        .build()
+        .unwrap();
+    assert_eq!(receipt.total, 550);
+    assert_eq!(receipt.transaction_id, "tx_0000");
+
+    let error = Receipt::for_transaction(&transaction, "receipt_0000")
+        .item(Item::new("Coffee", Money::new(300, Currency::GBP)))
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        error,
+        InvalidReceipt::TotalMismatch {
+            items: 300,
+            total: 550
+        }
+    );
+}
+
+#[tokio::test]
+async fn get_and_delete_receipt() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/transaction-receipts"))
+        .and(query_param("external_id", "receipt_0000"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "receipt": receipt() })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/transaction-receipts"))
+        .and(query_param("external_id", "receipt_0000"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Client::builder().url(server.uri()).build("ACCESS_TOKEN");
+
+    let receipt = client.receipt("receipt_0000").await.unwrap();
+    assert_eq!(receipt.items.len(), 2);
+
+    client.delete_receipt("receipt_0000").await.unwrap();
+}
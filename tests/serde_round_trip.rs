@@ -0,0 +1,383 @@
+//! Property tests checking that models serialize to JSON which deserializes
+//! back to the same value
+
+use std::fmt::Debug;
+
+use chrono::DateTime;
+use monzo::{
+    webhooks::parse_event, Account, Attachment, Balance, Pot, Transaction, Webhook, WhoAmI,
+};
+use proptest::{option, prelude::*};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Map, Value};
+
+/// Deserialize a model from generated JSON, then check it survives a round
+/// trip through its own JSON representation
+fn round_trip<T>(json: Value) -> Result<(), TestCaseError>
+where
+    T: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    let value: T = serde_json::from_value(json).map_err(|e| TestCaseError::fail(e.to_string()))?;
+    let serialized = serde_json::to_value(&value).unwrap();
+    let deserialized: T = serde_json::from_value(serialized).unwrap();
+    prop_assert_eq!(deserialized, value);
+    Ok(())
+}
+
+/// Add a field which isn't modelled by any struct
+fn with_unmodelled_field(mut json: Value, value: Value) -> Value {
+    json.as_object_mut()
+        .unwrap()
+        .insert("unmodelled_field".to_string(), value);
+    json
+}
+
+fn id() -> impl Strategy<Value = String> {
+    "[a-z]{2,5}_[0-9A-Za-z]{0,12}"
+}
+
+fn text() -> impl Strategy<Value = String> {
+    any::<String>()
+}
+
+fn timestamp() -> impl Strategy<Value = String> {
+    (0..4_102_444_800_i64, 0..1_000_000_000_u32)
+        .prop_map(|(secs, nanos)| DateTime::from_timestamp(secs, nanos).unwrap().to_rfc3339())
+}
+
+fn currency() -> impl Strategy<Value = String> {
+    prop_oneof![Just("GBP".to_string()), "[A-Z]{3}"]
+}
+
+fn category() -> impl Strategy<Value = String> {
+    prop_oneof![
+        Just("general".to_string()),
+        Just("eating_out".to_string()),
+        Just("bills".to_string()),
+        "[a-z_]{1,12}",
+    ]
+}
+
+fn unmodelled() -> impl Strategy<Value = Value> {
+    prop_oneof![
+        Just(Value::Null),
+        any::<bool>().prop_map(Value::from),
+        any::<i64>().prop_map(Value::from),
+        text().prop_map(Value::from),
+    ]
+}
+
+fn object() -> impl Strategy<Value = Map<String, Value>> {
+    prop::collection::btree_map("[a-z_]{1,8}", unmodelled(), 0..3)
+        .prop_map(|map| map.into_iter().collect())
+}
+
+fn attachment() -> impl Strategy<Value = Value> {
+    (id(), id(), id(), text(), text(), timestamp(), unmodelled()).prop_map(
+        |(id, user_id, external_id, file_url, file_type, created, extra)| {
+            with_unmodelled_field(
+                json!({
+                    "id": id,
+                    "user_id": user_id,
+                    "external_id": external_id,
+                    "file_url": file_url,
+                    "file_type": file_type,
+                    "created": created,
+                }),
+                extra,
+            )
+        },
+    )
+}
+
+fn merchant() -> impl Strategy<Value = Value> {
+    let address = (
+        (text(), text(), text(), text(), text()),
+        (
+            proptest::num::f32::NORMAL | proptest::num::f32::ZERO,
+            proptest::num::f32::NORMAL | proptest::num::f32::ZERO,
+        ),
+        unmodelled(),
+    )
+        .prop_map(
+            |((address, city, country, postcode, region), (latitude, longitude), extra)| {
+                with_unmodelled_field(
+                    json!({
+                        "address": address,
+                        "city": city,
+                        "country": country,
+                        "latitude": latitude,
+                        "longitude": longitude,
+                        "postcode": postcode,
+                        "region": region,
+                    }),
+                    extra,
+                )
+            },
+        );
+
+    (
+        address,
+        timestamp(),
+        (id(), id(), text(), text(), text()),
+        category(),
+        unmodelled(),
+    )
+        .prop_map(
+            |(address, created, (group_id, id, logo, emoji, name), category, extra)| {
+                with_unmodelled_field(
+                    json!({
+                        "address": address,
+                        "created": created,
+                        "group_id": group_id,
+                        "id": id,
+                        "logo": logo,
+                        "emoji": emoji,
+                        "name": name,
+                        "category": category,
+                    }),
+                    extra,
+                )
+            },
+        )
+}
+
+fn counterparty() -> impl Strategy<Value = Value> {
+    (
+        option::of("[0-9]{8}"),
+        option::of("[0-9]{6}"),
+        option::of(text()),
+        option::of(id()),
+    )
+        .prop_map(|(account_number, sort_code, name, user_id)| {
+            json!({
+                "account_number": account_number,
+                "sort_code": sort_code,
+                "name": name,
+                "user_id": user_id,
+            })
+        })
+}
+
+prop_compose! {
+    fn transaction()(
+        (id, account_id, description, notes) in (id(), id(), text(), text()),
+        (amount, local_amount, local_currency) in (any::<i64>(), option::of(any::<i64>()), option::of(currency())),
+        flags in any::<[bool; 8]>(),
+        (created, settled, updated) in (timestamp(), prop_oneof![Just(String::new()), timestamp()], option::of(timestamp())),
+        (currency, category) in (currency(), category()),
+        categories in prop::collection::hash_map(category(), any::<i64>(), 0..3),
+        merchant in option::of(prop_oneof![id().prop_map(Value::from), merchant()]),
+        metadata in prop::collection::hash_map("[a-z_]{1,8}", text(), 0..3),
+        decline_reason in option::of(prop_oneof![Just("INSUFFICIENT_FUNDS"), Just("OTHER")]),
+        attachments in prop::collection::vec(attachment(), 0..2),
+        counterparty in prop_oneof![Just(json!({})), counterparty()],
+        scheme in option::of(prop_oneof![Just("mastercard".to_string()), "[a-z_]{1,12}"]),
+        (fees, international) in (object(), option::of(object())),
+        labels in prop::collection::vec(text(), 0..3),
+        (dedupe_id, user_id) in (prop_oneof![Just(String::new()), id()], prop_oneof![Just(String::new()), id()]),
+        extra in unmodelled(),
+    ) -> Value {
+        let [amount_is_pending, can_add_to_tab, can_be_excluded_from_breakdown, can_be_made_subscription, can_split_the_bill, include_in_spending, is_load, originator] = flags;
+        with_unmodelled_field(json!({
+            "id": id,
+            "account_id": account_id,
+            "amount": amount,
+            "amount_is_pending": amount_is_pending,
+            "attachments": attachments,
+            "can_add_to_tab": can_add_to_tab,
+            "can_be_excluded_from_breakdown": can_be_excluded_from_breakdown,
+            "can_be_made_subscription": can_be_made_subscription,
+            "can_split_the_bill": can_split_the_bill,
+            "category": category,
+            "categories": categories,
+            "created": created,
+            "currency": currency,
+            "description": description,
+            "include_in_spending": include_in_spending,
+            "merchant": merchant,
+            "metadata": metadata,
+            "notes": notes,
+            "decline_reason": decline_reason,
+            "is_load": is_load,
+            "settled": settled,
+            "updated": updated,
+            "local_amount": local_amount,
+            "local_currency": local_currency,
+            "counterparty": counterparty,
+            "scheme": scheme,
+            "fees": fees,
+            "labels": labels,
+            "dedupe_id": dedupe_id,
+            "user_id": user_id,
+            "originator": originator,
+            "international": international,
+        }), extra)
+    }
+}
+
+prop_compose! {
+    fn account()(
+        (id, description, created) in (id(), text(), timestamp()),
+        account_type in prop_oneof![
+            Just("uk_retail"),
+            Just("uk_retail_joint"),
+            Just("uk_business"),
+            Just("uk_rewards"),
+            Just("uk_monzo_flex"),
+            Just("uk_loan"),
+        ],
+        (account_number, sort_code) in ("[0-9]{8}", "[0-9]{6}"),
+        closed in any::<bool>(),
+        (currency, country_code) in (currency(), "[A-Z]{2}"),
+        owners in prop::collection::vec((id(), text(), text(), unmodelled()), 0..3),
+        business_id in option::of(id()),
+        extra in unmodelled(),
+    ) -> Value {
+        let owners: Vec<_> = owners
+            .into_iter()
+            .map(|(user_id, preferred_name, preferred_first_name, extra)| {
+                with_unmodelled_field(
+                    json!({
+                        "user_id": user_id,
+                        "preferred_name": preferred_name,
+                        "preferred_first_name": preferred_first_name,
+                    }),
+                    extra,
+                )
+            })
+            .collect();
+
+        let mut account = json!({
+            "id": id,
+            "type": account_type,
+            "closed": closed,
+            "created": created,
+            "description": description,
+            "currency": currency,
+            "country_code": country_code,
+            "owners": owners,
+            "business_id": business_id,
+        });
+        if matches!(account_type, "uk_retail" | "uk_retail_joint" | "uk_business") {
+            account["account_number"] = account_number.into();
+            account["sort_code"] = sort_code.into();
+        }
+        with_unmodelled_field(account, extra)
+    }
+}
+
+prop_compose! {
+    fn pot()(
+        (id, name, current_account_id) in (id(), text(), id()),
+        style in prop_oneof![Just(String::new()), "[a-z_]{1,12}"],
+        (balance, goal_amount, currency) in (any::<i64>(), option::of(any::<i64>()), currency()),
+        (created, updated) in (timestamp(), timestamp()),
+        deleted in any::<bool>(),
+        extra in unmodelled(),
+    ) -> Value {
+        with_unmodelled_field(json!({
+            "id": id,
+            "name": name,
+            "style": style,
+            "balance": balance,
+            "currency": currency,
+            "goal_amount": goal_amount,
+            "current_account_id": current_account_id,
+            "created": created,
+            "updated": updated,
+            "deleted": deleted,
+        }), extra)
+    }
+}
+
+proptest! {
+    #[test]
+    fn transaction_round_trip(json in transaction()) {
+        round_trip::<Transaction>(json)?;
+    }
+
+    #[test]
+    fn account_round_trip(json in account()) {
+        round_trip::<Account>(json)?;
+    }
+
+    #[test]
+    fn pot_round_trip(json in pot()) {
+        round_trip::<Pot>(json)?;
+    }
+
+    #[test]
+    fn attachment_round_trip(json in attachment()) {
+        round_trip::<Attachment>(json)?;
+    }
+
+    #[test]
+    fn balance_round_trip(
+        (balance, total_balance, spend_today) in any::<(i64, i64, i64)>(),
+        balance_including_flexible_savings in option::of(any::<i64>()),
+        currency in currency(),
+        (local_currency, local_exchange_rate) in prop_oneof![
+            Just((String::new(), 0.0)),
+            (currency(), proptest::num::f64::POSITIVE | proptest::num::f64::NORMAL),
+        ],
+        local_spend in prop::collection::vec((any::<i64>(), currency()), 0..3),
+        extra in unmodelled(),
+    ) {
+        let local_spend: Vec<_> = local_spend
+            .into_iter()
+            .map(|(spend_today, currency)| json!({ "spend_today": spend_today, "currency": currency }))
+            .collect();
+
+        round_trip::<Balance>(with_unmodelled_field(json!({
+            "balance": balance,
+            "total_balance": total_balance,
+            "balance_including_flexible_savings": balance_including_flexible_savings,
+            "currency": currency,
+            "spend_today": spend_today,
+            "local_currency": local_currency,
+            "local_exchange_rate": local_exchange_rate,
+            "local_spend": local_spend,
+        }), extra))?;
+    }
+
+    #[test]
+    fn who_am_i_round_trip(
+        authenticated in any::<bool>(),
+        (client_id, user_id) in (id(), id()),
+        extra in unmodelled(),
+    ) {
+        round_trip::<WhoAmI>(with_unmodelled_field(json!({
+            "authenticated": authenticated,
+            "client_id": client_id,
+            "user_id": user_id,
+        }), extra))?;
+    }
+
+    #[test]
+    fn webhook_round_trip(
+        (id, account_id, url) in (id(), id(), text()),
+        extra in unmodelled(),
+    ) {
+        round_trip::<Webhook>(with_unmodelled_field(json!({
+            "id": id,
+            "account_id": account_id,
+            "url": url,
+        }), extra))?;
+    }
+
+    #[test]
+    fn event_round_trip(
+        event_type in prop_oneof![
+            Just("transaction.created".to_string()),
+            Just("transaction.updated".to_string()),
+            "[a-z_]{1,8}\\.[a-z_]{1,8}",
+        ],
+        data in transaction(),
+    ) {
+        let body = serde_json::to_vec(&json!({ "type": event_type, "data": data })).unwrap();
+        let event = parse_event(&body).unwrap();
+        let serialized = serde_json::to_vec(&event).unwrap();
+        prop_assert_eq!(parse_event(&serialized).unwrap(), event);
+    }
+}
@@ -0,0 +1,129 @@
+use futures_util::TryStreamExt;
+use monzo::Client;
+use serde_json::{json, Value};
+use wiremock::{
+    matchers::{body_string, method, path, query_param, query_param_is_missing},
+    Mock, MockServer, ResponseTemplate,
+};
+
+fn transaction(id: &str, created: &str) -> Value {
+    json!({
+        "id": id,
+        "account_id": "acc_0000",
+        "amount": -500,
+        "amount_is_pending": false,
+        "can_add_to_tab": false,
+        "can_be_excluded_from_breakdown": false,
+        "can_be_made_subscription": false,
+        "can_split_the_bill": false,
+        "category": "general",
+        "created": created,
+        "currency": "GBP",
+        "description": "description",
+        "include_in_spending": true,
+        "merchant": null,
+        "metadata": {},
+        "notes": "",
+        "is_load": false,
+        "settled": ""
+    })
+}
+
+fn page(transactions: &[Value]) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(json!({ "transactions": transactions }))
+}
+
+#[tokio::test]
+async fn stream() {
+    let server = MockServer::start().await;
+
+    Mock::given(path("/transactions"))
+        .and(query_param("limit", "2"))
+        .and(query_param_is_missing("since"))
+        .respond_with(page(&[
+            transaction("tx_1", "2024-01-01T00:00:00Z"),
+            transaction("tx_2", "2024-01-02T00:00:00Z"),
+        ]))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(path("/transactions"))
+        .and(query_param("limit", "2"))
+        .and(query_param("since", "tx_2"))
+        .respond_with(page(&[transaction("tx_3", "2024-01-03T00:00:00Z")]))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Client::builder().url(server.uri()).build("ACCESS_TOKEN");
+
+    let ids: Vec<_> = client
+        .transactions("acc_0000")
+        .limit(2)
+        .stream()
+        .map_ok(|transaction| transaction.id)
+        .try_collect()
+        .await
+        .unwrap();
+
+    assert_eq!(ids, ["tx_1", "tx_2", "tx_3"]);
+}
+
+#[tokio::test]
+async fn stream_stops_at_before_bound() {
+    let server = MockServer::start().await;
+
+    Mock::given(path("/transactions"))
+        .respond_with(page(&[
+            transaction("tx_1", "2024-01-01T00:00:00Z"),
+            transaction("tx_2", "2024-01-02T00:00:00Z"),
+        ]))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Client::builder().url(server.uri()).build("ACCESS_TOKEN");
+
+    let transactions: Vec<_> = client
+        .transactions("acc_0000")
+        .before("2024-01-02T00:00:00Z".parse().unwrap())
+        .limit(2)
+        .stream()
+        .try_collect()
+        .await
+        .unwrap();
+
+    assert_eq!(transactions.len(), 2);
+}
+
+#[tokio::test]
+async fn annotate() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("PATCH"))
+        .and(path("/transactions/tx_1"))
+        .and(body_string(
+            "metadata%5Bcost_centre%5D=CC-42&metadata%5Bproject%5D=",
+        ))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(
+                json!({ "transaction": transaction("tx_1", "2024-01-01T00:00:00Z") }),
+            ),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Client::builder().url(server.uri()).build("ACCESS_TOKEN");
+
+    let transaction = client
+        .annotate_transaction("tx_1")
+        .metadata("cost_centre", "CC-42")
+        .remove_metadata("project")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(transaction.id, "tx_1");
+}
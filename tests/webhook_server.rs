@@ -0,0 +1,199 @@
+#![cfg(feature = "webhook-server")]
+
+use std::{convert::Infallible, net::SocketAddr};
+
+use monzo::webhooks::{Event, Server};
+use reqwest::StatusCode;
+use serde_json::json;
+use tokio::{sync::mpsc, task::JoinHandle};
+
+fn transaction_created() -> serde_json::Value {
+    json!({
+        "type": "transaction.created",
+        "data": {
+            "id": "tx_0000",
+            "account_id": "acc_0000",
+            "amount": -350,
+            "amount_is_pending": true,
+            "can_add_to_tab": false,
+            "can_be_excluded_from_breakdown": true,
+            "can_be_made_subscription": false,
+            "can_split_the_bill": true,
+            "category": "eating_out",
+            "created": "2015-09-04T14:28:40Z",
+            "currency": "GBP",
+            "description": "Ozone Coffee Roasters",
+            "include_in_spending": true,
+            "is_load": false,
+            "merchant": null,
+            "metadata": {},
+            "notes": "",
+            "settled": ""
+        }
+    })
+}
+
+async fn start() -> (
+    SocketAddr,
+    mpsc::Receiver<Event>,
+    JoinHandle<std::io::Result<Infallible>>,
+) {
+    let server = Server::bind("127.0.0.1:0").await.unwrap().secret("SECRET");
+    let addr = server.local_addr().unwrap();
+
+    let (sender, receiver) = mpsc::channel(1);
+    let handle = tokio::spawn(server.serve_channel(sender));
+
+    (addr, receiver, handle)
+}
+
+#[tokio::test]
+async fn receive_event() {
+    let (addr, mut receiver, handle) = start().await;
+
+    let response = reqwest::Client::new()
+        .post(format!("http://{addr}/SECRET"))
+        .json(&transaction_created())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let event = receiver.recv().await.unwrap();
+    let Event::TransactionCreated(transaction) = event else {
+        panic!("expected a transaction.created event, got {event:?}");
+    };
+    assert_eq!(transaction.id, "tx_0000");
+
+    handle.abort();
+}
+
+#[tokio::test]
+async fn reject_invalid_requests() {
+    let (addr, mut receiver, handle) = start().await;
+    let client = reqwest::Client::new();
+
+    let response = client
+        .post(format!("http://{addr}/WRONG"))
+        .json(&transaction_created())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = client
+        .get(format!("http://{addr}/SECRET"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+
+    let response = client
+        .post(format!("http://{addr}/SECRET"))
+        .body("not json")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    assert!(receiver.try_recv().is_err());
+
+    handle.abort();
+}
+
+#[tokio::test]
+async fn slow_clients_time_out() {
+    use std::time::Duration;
+
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpStream,
+    };
+
+    let server = Server::bind("127.0.0.1:0")
+        .await
+        .unwrap()
+        .read_timeout(Duration::from_millis(100));
+    let addr = server.local_addr().unwrap();
+    let (sender, _receiver) = mpsc::channel(1);
+    let handle = tokio::spawn(server.serve_channel(sender));
+
+    // headers which are never finished
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    stream.write_all(b"POST / HTTP/1.1\r\n").await.unwrap();
+    let mut response = Vec::new();
+    tokio::time::timeout(Duration::from_secs(5), stream.read_to_end(&mut response))
+        .await
+        .expect("the connection should be closed")
+        .ok();
+
+    // a body which is never finished
+    let response = tokio::time::timeout(Duration::from_secs(5), async {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"POST / HTTP/1.1\r\nhost: localhost\r\ncontent-length: 100\r\n\r\n{")
+            .await
+            .unwrap();
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response).await.unwrap();
+        response
+    })
+    .await
+    .expect("the connection should be closed");
+    assert!(response.starts_with(b"HTTP/1.1 408"));
+
+    handle.abort();
+}
+
+#[tokio::test]
+async fn connection_limit() {
+    use std::time::Duration;
+
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpStream,
+    };
+
+    let server = Server::bind("127.0.0.1:0")
+        .await
+        .unwrap()
+        .max_connections(1);
+    let addr = server.local_addr().unwrap();
+    let (sender, mut receiver) = mpsc::channel(1);
+    let handle = tokio::spawn(server.serve_channel(sender));
+    let client = reqwest::Client::new();
+
+    // a connection which holds the only slot
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    stream.write_all(b"POST / HTTP/1.1\r\n").await.unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let response = client
+        .post(format!("http://{addr}/"))
+        .json(&transaction_created())
+        .timeout(Duration::from_millis(200))
+        .send()
+        .await;
+    assert!(response.is_err(), "the request should wait for a free slot");
+
+    drop(stream);
+    let response = client
+        .post(format!("http://{addr}/"))
+        .json(&transaction_created())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(receiver.recv().await.is_some());
+
+    // stopping the server closes open connections
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    stream.write_all(b"POST / HTTP/1.1\r\n").await.unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    handle.abort();
+    let mut response = Vec::new();
+    tokio::time::timeout(Duration::from_secs(5), stream.read_to_end(&mut response))
+        .await
+        .expect("the connection should be closed")
+        .ok();
+}
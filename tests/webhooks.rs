@@ -0,0 +1,72 @@
+use monzo::Client;
+use serde_json::json;
+use wiremock::{
+    matchers::{body_string, method, path, query_param},
+    Mock, MockServer, ResponseTemplate,
+};
+
+fn webhook() -> serde_json::Value {
+    json!({
+        "account_id": "acc_0000",
+        "id": "webhook_0000",
+        "url": "https://example.com/monzo"
+    })
+}
+
+#[tokio::test]
+async fn list() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/webhooks"))
+        .and(query_param("account_id", "acc_0000"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "webhooks": [webhook()] })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Client::builder().url(server.uri()).build("ACCESS_TOKEN");
+
+    let webhooks = client.webhooks("acc_0000").await.unwrap();
+    assert_eq!(webhooks.len(), 1);
+    assert_eq!(webhooks[0].id, "webhook_0000");
+}
+
+#[tokio::test]
+async fn register() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/webhooks"))
+        .and(body_string(
+            "account_id=acc_0000&url=https%3A%2F%2Fexample.com%2Fmonzo",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "webhook": webhook() })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Client::builder().url(server.uri()).build("ACCESS_TOKEN");
+
+    let webhook = client
+        .register_webhook("acc_0000", "https://example.com/monzo")
+        .await
+        .unwrap();
+    assert_eq!(webhook.url, "https://example.com/monzo");
+}
+
+#[tokio::test]
+async fn delete() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/webhooks/webhook_0000"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Client::builder().url(server.uri()).build("ACCESS_TOKEN");
+
+    client.delete_webhook("webhook_0000").await.unwrap();
+}